@@ -1,7 +1,26 @@
-use aoc_runner_derive::aoc;
+use aoc_common::{Day, parse::ParseError};
 
-#[aoc(day1, part1)]
-fn solve_part1(input: &str) -> i32 {
+pub struct Day1;
+
+impl Day for Day1 {
+    const DAY: u8 = 1;
+    type Parsed = String;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<String, ParseError> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &String) -> i64 {
+        solve_part1(input).into()
+    }
+
+    fn part2(input: &String) -> i64 {
+        solve_part2(input) as i64
+    }
+}
+
+pub fn solve_part1(input: &str) -> i32 {
     input
         .chars()
         .map(|c| match c {
@@ -12,8 +31,7 @@ fn solve_part1(input: &str) -> i32 {
         .sum()
 }
 
-#[aoc(day1, part2)]
-fn solve_part2(input: &str) -> usize {
+pub fn solve_part2(input: &str) -> usize {
     let mut floor = 0;
     for (idx, c) in input.chars().enumerate() {
         floor += match c {
@@ -1,5 +1,9 @@
-use aoc_runner_derive::aoc_lib;
+//! Advent of Code 2015, one public module per day with its parsed types,
+//! generator and solvers, plus a type implementing [`aoc_common::Day`].
 
-mod day1;
+#[cfg(feature = "day1")]
+pub mod day1;
 
-aoc_lib! { year = 2015 }
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+aoc_common::example_tests!();
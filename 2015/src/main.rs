@@ -1,3 +0,0 @@
-use aoc_runner_derive::aoc_main;
-
-aoc_main! { lib = aoc2015 }
@@ -1,17 +1,66 @@
-use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_common::{
+    Day, Variant,
+    parse::{self, ParseError},
+};
 
-#[aoc_generator(day1)]
-fn input_generator(input: &str) -> Vec<i32> {
+pub struct Day01;
+
+impl Day for Day01 {
+    const DAY: u8 = 1;
+    type Parsed = Vec<i32>;
+    type Answer = u32;
+
+    const PART1_NAME: Option<&'static str> = Some("decl");
+    const PART2_NAME: Option<&'static str> = Some("smart");
+
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Vec<i32>) -> u32 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Vec<i32>) -> u32 {
+        solve_part2(input)
+    }
+
+    fn variants() -> &'static [Variant<Self>] {
+        &[
+            Variant {
+                part: 1,
+                name: "iterator",
+                solve: |input| solve_part1_it(input),
+            },
+            Variant {
+                part: 2,
+                name: "bruteforce",
+                solve: |input| solve_part2_bf(input),
+            },
+            Variant {
+                part: 2,
+                name: "iterator",
+                solve: |input| solve_part2_it(input),
+            },
+        ]
+    }
+}
+
+pub fn input_generator(input: &str) -> Result<Vec<i32>, ParseError> {
     input
         .lines()
         .map(|line| {
-            (if line.starts_with('L') { -1 } else { 1 }) * line[1..].parse::<i32>().unwrap()
+            let sign = match line.chars().next() {
+                Some('L') => -1,
+                Some('R') => 1,
+                _ => return Err(ParseError::at(1, input, line, "`L` or `R`")),
+            };
+            Ok(sign * parse::number::<i32>(1, input, &line[1..])?)
         })
         .collect()
 }
 
-#[aoc(day1, part1, decl)]
-fn solve_part1(input: &[i32]) -> u32 {
+pub fn solve_part1(input: &[i32]) -> u32 {
     let mut position = 50;
     let mut count = 0;
     for &val in input {
@@ -23,8 +72,7 @@ fn solve_part1(input: &[i32]) -> u32 {
     count
 }
 
-#[aoc(day1, part1, iterator)]
-fn solve_part1_it(input: &[i32]) -> u32 {
+pub fn solve_part1_it(input: &[i32]) -> u32 {
     input
         .iter()
         .fold((50, 0), |(mut position, mut count), &val| {
@@ -35,8 +83,7 @@ fn solve_part1_it(input: &[i32]) -> u32 {
         .1
 }
 
-#[aoc(day1, part2, smart)]
-fn solve_part2(input: &[i32]) -> u32 {
+pub fn solve_part2(input: &[i32]) -> u32 {
     let mut position = 50;
     let mut count = 0;
     for &val in input {
@@ -48,8 +95,7 @@ fn solve_part2(input: &[i32]) -> u32 {
     count
 }
 
-#[aoc(day1, part2, bruteforce)]
-fn solve_part2_bf(input: &[i32]) -> u32 {
+pub fn solve_part2_bf(input: &[i32]) -> u32 {
     let mut position = 50;
     let mut count = 0;
     for &val in input {
@@ -64,8 +110,7 @@ fn solve_part2_bf(input: &[i32]) -> u32 {
     count
 }
 
-#[aoc(day1, part2, iterator)]
-fn solve_part2_it(input: &[i32]) -> u32 {
+pub fn solve_part2_it(input: &[i32]) -> u32 {
     input
         .iter()
         .fold((50, 0), |(mut position, mut count), &val| {
@@ -77,62 +122,3 @@ fn solve_part2_it(input: &[i32]) -> u32 {
         })
         .1
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_day1_part1() {
-        let input = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82";
-        assert_eq!(solve_part1(&input_generator(input)), 3);
-    }
-
-    #[test]
-    fn test_day1_part2() {
-        let input = "L68
-L30
-R48
-L5
-R60
-L55
-L1
-L99
-R14
-L82";
-        assert_eq!(solve_part2(&input_generator(input)), 6);
-    }
-
-    #[test]
-    fn test_day1_part2_2() {
-        let input = "R1000";
-        assert_eq!(solve_part2(&input_generator(input)), 10);
-    }
-
-    #[test]
-    fn test_day1_part2_long() {
-        let input = "R100000000
-R100000000
-R100000000
-R100000000";
-        assert_eq!(solve_part2_bf(&input_generator(input)), 4_000_000);
-        assert_eq!(solve_part2(&input_generator(input)), 4_000_000);
-    }
-
-    #[test]
-    fn test_day1_part2_3() {
-        let input = "L50
-L100";
-        let parsed = input_generator(input);
-        assert_eq!(solve_part2(&parsed), solve_part2_bf(&parsed));
-    }
-}
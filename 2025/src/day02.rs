@@ -1,4 +1,7 @@
-use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_common::{
+    Day, cancel,
+    parse::{ParseError, expected},
+};
 use pathfinding::num_traits::Euclid;
 use winnow::{
     Parser, Result,
@@ -6,22 +9,54 @@ use winnow::{
     combinator::{separated, separated_pair},
 };
 
+pub struct Day02;
+
+impl Day for Day02 {
+    const DAY: u8 = 2;
+    type Parsed = Vec<(u64, u64)>;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &Vec<(u64, u64)>) -> u64 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &Vec<(u64, u64)>) -> u64 {
+        solve_part2(input)
+    }
+}
+
 fn range(input: &mut &str) -> Result<(u64, u64)> {
-    separated_pair(dec_uint, '-', dec_uint).parse_next(input)
+    separated_pair(
+        dec_uint.context(expected("a number")),
+        '-'.context(expected("`-`")),
+        dec_uint.context(expected("a number")),
+    )
+    .parse_next(input)
 }
 
-#[aoc_generator(day2)]
-fn input_generator(input: &str) -> Vec<(u64, u64)> {
+pub fn input_generator(input: &str) -> Result<Vec<(u64, u64)>, ParseError> {
     let mut parser = separated(1.., range, ',');
 
-    parser.parse(input).expect("Could not parse input")
+    parser
+        .parse(input)
+        .map_err(|e| ParseError::winnow(2, input, &e))
 }
 
-#[aoc(day2, part1)]
-fn solve_part1(input: &[(u64, u64)]) -> u64 {
+pub fn solve_part1(input: &[(u64, u64)]) -> u64 {
     input
         .iter()
-        .map(|&(start, end)| (start..=end).filter(|&id| !is_valid_id_1(id)).sum::<u64>())
+        .map(|&(start, end)| {
+            (start..=end)
+                .filter(|&id| {
+                    cancel::checkpoint();
+                    !is_valid_id_1(id)
+                })
+                .sum::<u64>()
+        })
         .sum()
 }
 
@@ -60,11 +95,17 @@ fn is_valid_id_2(id: u64) -> bool {
     !invalid
 }
 
-#[aoc(day2, part2)]
-fn solve_part2(input: &[(u64, u64)]) -> u64 {
+pub fn solve_part2(input: &[(u64, u64)]) -> u64 {
     input
         .iter()
-        .map(|&(start, end)| (start..=end).filter(|&id| !is_valid_id_2(id)).sum::<u64>())
+        .map(|&(start, end)| {
+            (start..=end)
+                .filter(|&id| {
+                    cancel::checkpoint();
+                    !is_valid_id_2(id)
+                })
+                .sum::<u64>()
+        })
         .sum()
 }
 
@@ -98,18 +139,4 @@ mod tests {
     fn test_day2_is_valid_id_2(#[case] id: u64, #[case] valid: bool) {
         assert_eq!(is_valid_id_2(id), valid);
     }
-
-    #[test]
-    fn test_day2_part1() {
-        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
-        let parsed = input_generator(input);
-        assert_eq!(solve_part1(&parsed), 1_227_775_554);
-    }
-
-    #[test]
-    fn test_day2_part2() {
-        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
-        let parsed = input_generator(input);
-        assert_eq!(solve_part1(&parsed), 44_487_518_055);
-    }
 }
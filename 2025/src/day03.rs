@@ -1,4 +1,47 @@
-use aoc_runner_derive::aoc;
+use aoc_common::{Day, assume::Assumption, parse::ParseError};
+
+pub struct Day03;
+
+impl Day for Day03 {
+    const DAY: u8 = 3;
+    type Parsed = String;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<String, ParseError> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &String) -> u64 {
+        solve_part1(input).into()
+    }
+
+    fn part2(input: &String) -> u64 {
+        solve_part2(input)
+    }
+
+    fn assumptions(input: &String) -> Vec<Assumption> {
+        assumptions(input)
+    }
+}
+
+fn assumptions(input: &str) -> Vec<Assumption> {
+    let lines = || input.lines().enumerate().map(|(idx, line)| (idx + 1, line));
+    vec![
+        Assumption::unless(
+            "every bank holds only digits",
+            lines().find_map(|(n, line)| {
+                let c = line.chars().find(|c| !c.is_ascii_digit())?;
+                Some(format!("line {n} has `{c}`"))
+            }),
+        ),
+        Assumption::unless(
+            "every bank has at least 12 batteries",
+            lines()
+                .find(|(_, line)| line.len() < 12)
+                .map(|(n, line)| format!("line {n} has {}", line.len())),
+        ),
+    ]
+}
 
 fn first_max(input: &str) -> (usize, char) {
     let mut max_idx = 0;
@@ -12,8 +55,7 @@ fn first_max(input: &str) -> (usize, char) {
     (max_idx, max)
 }
 
-#[aoc(day3, part1)]
-fn solve_part1(input: &str) -> u32 {
+pub fn solve_part1(input: &str) -> u32 {
     input
         .lines()
         .map(|line| {
@@ -24,8 +66,7 @@ fn solve_part1(input: &str) -> u32 {
         .sum()
 }
 
-#[aoc(day3, part2)]
-fn solve_part2(input: &str) -> u64 {
+pub fn solve_part2(input: &str) -> u64 {
     input
         .lines()
         .map(|line| {
@@ -42,26 +83,3 @@ fn solve_part2(input: &str) -> u64 {
         })
         .sum()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_day3_part1() {
-        let input = "987654321111111
-811111111111119
-234234234234278
-818181911112111";
-        assert_eq!(solve_part1(input), 357);
-    }
-
-    #[test]
-    fn test_day3_part2() {
-        let input = "987654321111111
-811111111111119
-234234234234278
-818181911112111";
-        assert_eq!(solve_part2(input), 3_121_910_778_619);
-    }
-}
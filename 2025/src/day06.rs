@@ -1,7 +1,26 @@
-use aoc_runner_derive::aoc;
+use aoc_common::{Day, parse::ParseError};
 
-#[aoc(day6, part1)]
-fn solve_part1(input: &str) -> u64 {
+pub struct Day06;
+
+impl Day for Day06 {
+    const DAY: u8 = 6;
+    type Parsed = String;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<String, ParseError> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &String) -> u64 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &String) -> u64 {
+        solve_part2(input)
+    }
+}
+
+pub fn solve_part1(input: &str) -> u64 {
     let input = input.lines().collect::<Vec<_>>();
 
     let mut probs = input[input.len() - 1]
@@ -31,8 +50,7 @@ fn solve_part1(input: &str) -> u64 {
     probs.iter().map(|(_, answer)| *answer).sum()
 }
 
-#[aoc(day6, part2)]
-fn solve_part2(input: &str) -> u64 {
+pub fn solve_part2(input: &str) -> u64 {
     let mut input = input
         .lines()
         .map(|line| {
@@ -80,26 +98,3 @@ fn solve_part2(input: &str) -> u64 {
 
     total
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_day6_part1() {
-        let input = "123 328  51 64 
- 45 64  387 23 
-  6 98  215 314
-*   +   *   +  ";
-        assert_eq!(solve_part1(input), 4_277_556);
-    }
-
-    #[test]
-    fn test_day6_part2() {
-        let input = "123 328  51 64 
- 45 64  387 23 
-  6 98  215 314
-*   +   *   +  ";
-        assert_eq!(solve_part2(input), 3_263_827);
-    }
-}
@@ -1,7 +1,52 @@
-use aoc_runner_derive::aoc;
+use aoc_common::{Day, assume::Assumption, parse::ParseError};
 
-#[aoc(day7, part1)]
-fn solve_part1(input: &str) -> u32 {
+pub struct Day07;
+
+impl Day for Day07 {
+    const DAY: u8 = 7;
+    type Parsed = String;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<String, ParseError> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &String) -> u64 {
+        solve_part1(input).into()
+    }
+
+    fn part2(input: &String) -> u64 {
+        solve_part2(input)
+    }
+
+    fn assumptions(input: &String) -> Vec<Assumption> {
+        assumptions(input)
+    }
+}
+
+fn assumptions(input: &str) -> Vec<Assumption> {
+    let second = input.lines().nth(1);
+    vec![
+        Assumption::unless(
+            "the second line is empty",
+            match second {
+                Some(line) => line
+                    .chars()
+                    .position(|c| c != '.')
+                    .map(|idx| format!("column {} is not empty", idx + 1)),
+                None => Some("there is none".to_owned()),
+            },
+        ),
+        Assumption::unless(
+            "no splitter on the left or right edge",
+            input.lines().enumerate().find_map(|(idx, line)| {
+                (line.starts_with('^') || line.ends_with('^')).then(|| format!("line {}", idx + 1))
+            }),
+        ),
+    ]
+}
+
+pub fn solve_part1(input: &str) -> u32 {
     let mut rows = input.lines();
     let mut beams = rows
         .next()
@@ -27,8 +72,7 @@ fn solve_part1(input: &str) -> u32 {
     splits
 }
 
-#[aoc(day7, part2)]
-fn solve_part2(input: &str) -> u64 {
+pub fn solve_part2(input: &str) -> u64 {
     let mut rows = input.lines();
     let mut beams = rows
         .next()
@@ -54,47 +98,19 @@ fn solve_part2(input: &str) -> u64 {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use rstest::rstest;
 
-    #[test]
-    fn test_day7_part1() {
-        let input = ".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............";
-        assert_eq!(solve_part1(input), 21);
-    }
+    use super::*;
 
-    #[test]
-    fn test_day7_part2() {
-        let input = ".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............";
-        assert_eq!(solve_part2(input), 40);
+    #[rstest]
+    #[case(include_str!("../../examples/2025/day07/example.txt"), [None, None])]
+    #[case("..S..\n..^..\n.^.^.", [Some("column 3 is not empty"), None])]
+    #[case("..S..\n.....\n^...^", [None, Some("line 3")])]
+    fn test_assumptions(#[case] input: &str, #[case] expected: [Option<&str>; 2]) {
+        let violations = assumptions(input)
+            .into_iter()
+            .map(|a| a.violation)
+            .collect::<Vec<_>>();
+        assert_eq!(violations, expected.map(|v| v.map(str::to_owned)));
     }
 }
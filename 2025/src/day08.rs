@@ -1,169 +1,283 @@
-use aoc_runner_derive::{aoc, aoc_generator};
+use std::fmt::Write;
+
+use aoc_common::{
+    Day,
+    assume::Assumption,
+    disjoint_set::DisjointSet,
+    parse::{self, ParseError},
+};
 use euclid::default::Point3D;
-use indicatif::ProgressIterator;
 use itertools::Itertools;
 
-type ParsedInput = Vec<Point3D<f32>>;
+pub type ParsedInput = Vec<Point3D<i64>>;
+
+pub struct Day08;
+
+impl Day for Day08 {
+    const DAY: u8 = 8;
+    type Parsed = ParsedInput;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<ParsedInput, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &ParsedInput) -> u64 {
+        solve_part1(input).into()
+    }
 
-#[aoc_generator(day8)]
-fn input_generator(input: &str) -> ParsedInput {
+    fn part2(input: &ParsedInput) -> u64 {
+        solve_part2(input)
+    }
+
+    fn assumptions(input: &ParsedInput) -> Vec<Assumption> {
+        vec![Assumption::unless(
+            "there are at least two junctions",
+            (input.len() < 2).then(|| format!("only {}", input.len())),
+        )]
+    }
+}
+
+pub fn input_generator(input: &str) -> Result<ParsedInput, ParseError> {
     input
         .lines()
         .map(|line| {
+            let end = &line[line.len()..];
             let mut split = line.split(',');
-            Point3D::new(
-                split.next().unwrap().parse::<f32>().unwrap(),
-                split.next().unwrap().parse::<f32>().unwrap(),
-                split.next().unwrap().parse::<f32>().unwrap(),
-            )
+            let mut coordinate = || {
+                let field = split
+                    .next()
+                    .ok_or_else(|| ParseError::at(8, input, end, "`,`"))?;
+                parse::number::<i64>(8, input, field)
+            };
+            let point = Point3D::new(coordinate()?, coordinate()?, coordinate()?);
+            match split.next() {
+                Some(extra) => Err(ParseError::at(8, input, extra, "end of line")),
+                None => Ok(point),
+            }
         })
         .collect()
 }
 
-#[aoc(day8, part1)]
-fn solve_part1(input: &ParsedInput) -> u32 {
-    solve_part1_inner(input, 1000)
+/// Every pair of junctions `(i, j)` with `i < j`, closest first, along with
+/// their squared distance.
+pub fn pairs_by_distance(input: &ParsedInput) -> Vec<(i64, usize, usize)> {
+    let mut pairs = input
+        .iter()
+        .enumerate()
+        .tuple_combinations()
+        .map(|((i, p1), (j, p2))| ((*p2 - *p1).square_length(), i, j))
+        .collect::<Vec<_>>();
+    pairs.sort_unstable();
+    pairs
 }
 
-fn solve_part1_inner(input: &ParsedInput, num_connections: u32) -> u32 {
-    let mut junctions = input.iter().enumerate().collect::<Vec<_>>();
-    let mut connected_pairs = std::collections::HashSet::new();
-
-    for _ in (0..num_connections).progress() {
-        let mut min_pair = (0, 0);
-        let mut min_dist = f32::MAX;
-        for (i, (_, p1)) in junctions.iter().enumerate() {
-            for (j, (_, p2)) in junctions.iter().enumerate() {
-                if i != j
-                    && !connected_pairs.contains(&(i, j))
-                    && !connected_pairs.contains(&(j, i))
-                {
-                    let dist = p1.distance_to(**p2);
-                    if dist < min_dist {
-                        min_dist = dist;
-                        min_pair = (i, j);
-                    }
-                }
-            }
-        }
-
-        connected_pairs.insert(min_pair);
-
-        let old_group = junctions[min_pair.1].0;
-        let new_group = junctions[min_pair.0].0;
+pub fn solve_part1(input: &ParsedInput) -> u32 {
+    solve_part1_inner(input, 1000)
+}
 
-        for (circuit, _) in &mut junctions {
-            if *circuit == old_group {
-                *circuit = new_group;
-            }
-        }
+/// The product of the three largest circuits after `num_connections`.
+pub fn solve_part1_inner(input: &ParsedInput, num_connections: u32) -> u32 {
+    let mut circuits = DisjointSet::new(input.len());
+    for (_, i, j) in pairs_by_distance(input)
+        .into_iter()
+        .take(num_connections as usize)
+    {
+        circuits.union(i, j);
     }
 
-    let mut group_size = std::collections::HashMap::<usize, u32>::new();
-    for (group, _) in junctions {
-        group_size
-            .entry(group)
-            .and_modify(|size| *size += 1)
-            .or_insert(1);
-    }
-    group_size
-        .values()
+    circuits
+        .components()
+        .map(|circuit| circuit.len() as u32)
         .sorted_unstable()
         .rev()
         .take(3)
         .product()
 }
 
-#[aoc(day8, part2)]
-fn solve_part2(input: &ParsedInput) -> u64 {
-    let mut junctions = input.iter().enumerate().collect::<Vec<_>>();
-
-    let mut min_pair = (0, 0);
-    loop {
-        let mut min_dist = f32::MAX;
-        for (i, (g1, p1)) in junctions.iter().enumerate() {
-            for (j, (g2, p2)) in junctions.iter().enumerate() {
-                if i != j && g1 != g2 {
-                    let dist = p1.distance_to(**p2);
-                    if dist < min_dist {
-                        min_dist = dist;
-                        min_pair = (i, j);
-                    }
+/// The last pair single-linkage clustering merges, or 0 for fewer than two
+/// junctions.
+pub fn solve_part2(input: &ParsedInput) -> u64 {
+    Dendrogram::new(input).merges().last().map_or(0, |merge| {
+        let (i, j) = merge.pair;
+        (input[i].x * input[j].x) as u64
+    })
+}
+
+/// One step of single-linkage clustering, joining two circuits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Merge {
+    /// How many of the closest pairs are connected once this one is,
+    /// counting those already in the same circuit.
+    pub connection: usize,
+    pub pair: (usize, usize),
+    pub distance_squared: i64,
+    /// The sizes of the circuits of each junction of `pair` before the merge.
+    pub sizes: (usize, usize),
+    /// How many circuits are left after the merge.
+    pub circuits: usize,
+}
+
+impl Merge {
+    pub fn distance(&self) -> f64 {
+        (self.distance_squared as f64).sqrt()
+    }
+}
+
+/// Every merge of single-linkage clustering over the junctions, that is
+/// Kruskal's algorithm connecting the closest pairs first.
+#[derive(Debug, Clone)]
+pub struct Dendrogram {
+    junctions: usize,
+    merges: Vec<Merge>,
+}
+
+impl Dendrogram {
+    pub fn new(input: &ParsedInput) -> Self {
+        let mut circuits = DisjointSet::new(input.len());
+        let mut merges = Vec::with_capacity(input.len().saturating_sub(1));
+        for (connection, (distance_squared, i, j)) in (1..).zip(pairs_by_distance(input)) {
+            let sizes = (circuits.size_of(i), circuits.size_of(j));
+            if circuits.union(i, j) {
+                merges.push(Merge {
+                    connection,
+                    pair: (i, j),
+                    distance_squared,
+                    sizes,
+                    circuits: circuits.count(),
+                });
+                if circuits.count() == 1 {
+                    break;
                 }
             }
         }
+        Self {
+            junctions: input.len(),
+            merges,
+        }
+    }
 
-        let old_group = junctions[min_pair.1].0;
-        let new_group = junctions[min_pair.0].0;
+    pub fn merges(&self) -> &[Merge] {
+        &self.merges
+    }
 
-        for (circuit, _) in &mut junctions {
-            if *circuit == old_group {
-                *circuit = new_group;
-            }
+    /// How many circuits there are once the `connections` closest pairs are
+    /// connected.
+    pub fn circuits_after(&self, connections: usize) -> usize {
+        self.junctions
+            - self
+                .merges
+                .partition_point(|merge| merge.connection <= connections)
+    }
+
+    /// The clustering as a Newick tree, with junctions labelled by their index
+    /// in the input and nodes at the height of their merge distance.
+    pub fn to_newick(&self) -> String {
+        // the subtree and its height, for each circuit by its representative
+        let mut nodes = (0..self.junctions)
+            .map(|i| (i.to_string(), 0.0))
+            .collect::<Vec<_>>();
+        let mut circuits = DisjointSet::new(self.junctions);
+        for merge in &self.merges {
+            let (i, j) = (circuits.find(merge.pair.0), circuits.find(merge.pair.1));
+            let ((left, left_height), (right, right_height)) =
+                (std::mem::take(&mut nodes[i]), std::mem::take(&mut nodes[j]));
+            let height = merge.distance();
+            circuits.union(i, j);
+            nodes[circuits.find(i)] = (
+                format!(
+                    "({left}:{},{right}:{})",
+                    height - left_height,
+                    height - right_height
+                ),
+                height,
+            );
         }
-        let group = junctions[0].0;
-        let all_same_group = junctions.iter().all(|(g, _)| *g == group);
-        if all_same_group {
-            break;
+        // every pair gets connected, so this is the single circuit left
+        match self.junctions {
+            0 => ";".to_string(),
+            _ => std::mem::take(&mut nodes[circuits.find(0)].0) + ";",
         }
     }
 
-    junctions[min_pair.0].1.x as u64 * junctions[min_pair.1].1.x as u64
+    /// One line per merge, after a header.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("connection,a,b,distance_squared,size_a,size_b,circuits\n");
+        for merge in &self.merges {
+            writeln!(
+                csv,
+                "{},{},{},{},{},{},{}",
+                merge.connection,
+                merge.pair.0,
+                merge.pair.1,
+                merge.distance_squared,
+                merge.sizes.0,
+                merge.sizes.1,
+                merge.circuits
+            )
+            .unwrap();
+        }
+        csv
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use super::*;
 
     #[test]
     fn test_day8_part1() {
-        let input = "162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689";
-        let parsed = input_generator(input);
+        let input = include_str!("../../examples/2025/day08/example.txt");
+        let parsed = input_generator(input).unwrap();
         assert_eq!(solve_part1_inner(&parsed, 10), 40);
     }
 
     #[test]
-    fn test_day8_part2() {
-        let input = "162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689";
-        let parsed = input_generator(input);
+    fn test_day8_dendrogram_example() {
+        let input = include_str!("../../examples/2025/day08/example.txt");
+        let parsed = input_generator(input).unwrap();
+        let dendrogram = Dendrogram::new(&parsed);
+        assert_eq!(dendrogram.merges().len(), parsed.len() - 1);
+        assert_eq!(dendrogram.circuits_after(0), 20);
+        assert_eq!(dendrogram.circuits_after(10), 11);
+        assert_eq!(dendrogram.circuits_after(usize::MAX), 1);
         assert_eq!(solve_part2(&parsed), 25272);
     }
+
+    #[test]
+    fn test_day8_dendrogram_exports() {
+        let parsed = input_generator("0,0,0\n1,0,0\n5,0,0\n1,1,0").unwrap();
+        let dendrogram = Dendrogram::new(&parsed);
+        assert_eq!(dendrogram.to_newick(), "(((0:1,1:1):0,3:1):3,2:4);");
+        assert_eq!(
+            dendrogram.to_csv(),
+            "connection,a,b,distance_squared,size_a,size_b,circuits\n\
+             1,0,1,1,1,1,3\n\
+             2,1,3,1,2,1,2\n\
+             4,1,2,16,3,1,1\n"
+        );
+    }
+
+    #[test]
+    fn test_day8_single_junction() {
+        let parsed = input_generator("162,817,812").unwrap();
+        assert_eq!(solve_part2(&parsed), 0);
+        let assumptions = Day08::assumptions(&parsed);
+        assert_eq!(assumptions[0].violation.as_deref(), Some("only 1"));
+    }
+
+    #[rstest]
+    #[case("162,817,812\n57,618", (2, 7, "`,`"))]
+    #[case("162,817,812,4", (1, 13, "end of line"))]
+    #[case("162,8x7,812", (1, 5, "a number"))]
+    fn test_day8_input_generator_error(
+        #[case] input: &str,
+        #[case] (line, column, expected): (usize, usize, &str),
+    ) {
+        let error = input_generator(input).unwrap_err();
+        assert_eq!((error.line, error.column), (line, column));
+        assert_eq!(error.expected, expected);
+    }
 }
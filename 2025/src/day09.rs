@@ -1,26 +1,59 @@
 use std::collections::{HashMap, HashSet};
 
-use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_common::{
+    Day,
+    assume::Assumption,
+    parse::{self, ParseError},
+};
 use itertools::Itertools;
 
-type ParsedInput = Vec<(u64, u64)>;
+pub type ParsedInput = Vec<(u64, u64)>;
 
-#[aoc_generator(day9)]
-fn input_generator(input: &str) -> ParsedInput {
+pub struct Day09;
+
+impl Day for Day09 {
+    const DAY: u8 = 9;
+    type Parsed = ParsedInput;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<ParsedInput, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &ParsedInput) -> u64 {
+        solve_part1(input)
+    }
+
+    fn part2(input: &ParsedInput) -> u64 {
+        solve_part2(input)
+    }
+
+    fn assumptions(tiles: &ParsedInput) -> Vec<Assumption> {
+        vec![Assumption::unless(
+            "consecutive tiles share a row or a column",
+            tiles
+                .iter()
+                .circular_tuple_windows()
+                .find(|(a, b)| a.0 != b.0 && a.1 != b.1)
+                .map(|(a, b)| format!("{},{} then {},{}", a.0, a.1, b.0, b.1)),
+        )]
+    }
+}
+
+pub fn input_generator(input: &str) -> Result<ParsedInput, ParseError> {
     input
         .lines()
         .map(|line| {
-            let mut split = line.split(',');
-            (
-                split.next().unwrap().parse::<u64>().unwrap(),
-                split.next().unwrap().parse::<u64>().unwrap(),
-            )
+            let (x, y) = line.split_once(',').ok_or_else(|| {
+                let end = line.find(|c: char| !c.is_ascii_digit());
+                ParseError::at(9, input, &line[end.unwrap_or(line.len())..], "`,`")
+            })?;
+            Ok((parse::number(9, input, x)?, parse::number(9, input, y)?))
         })
         .collect()
 }
 
-#[aoc(day9, part1)]
-fn solve_part1(input: &ParsedInput) -> u64 {
+pub fn solve_part1(input: &ParsedInput) -> u64 {
     input
         .iter()
         .cartesian_product(input)
@@ -29,8 +62,7 @@ fn solve_part1(input: &ParsedInput) -> u64 {
         .unwrap()
 }
 
-#[aoc(day9, part2)]
-fn solve_part2(input: &ParsedInput) -> u64 {
+pub fn solve_part2(input: &ParsedInput) -> u64 {
     // compress coordinates
     let xs = input.iter().map(|(x, _)| *x).collect::<HashSet<_>>();
     let xs = xs
@@ -70,6 +102,8 @@ fn solve_part2(input: &ParsedInput) -> u64 {
             // horizontal
             let x_min = x1.min(x2);
             let x_max = x1.max(x2);
+
+            #[allow(clippy::needless_range_loop)]
             for x in x_min..=x_max {
                 scale_map[y1][x] = true;
             }
@@ -112,36 +146,3 @@ fn solve_part2(input: &ParsedInput) -> u64 {
         .max()
         .unwrap()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_day9_part1() {
-        let input = "7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3";
-        let parsed = input_generator(input);
-        assert_eq!(solve_part1(&parsed), 50);
-    }
-
-    #[test]
-    fn test_day9_part2() {
-        let input = "7,1
-11,1
-11,7
-9,7
-9,5
-2,5
-2,3
-7,3";
-        let parsed = input_generator(input);
-        assert_eq!(solve_part2(&parsed), 24);
-    }
-}
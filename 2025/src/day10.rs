@@ -1,52 +1,68 @@
-use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_common::{
+    Day,
+    parse::{ParseError, expected},
+};
 use itertools::Itertools;
 use winnow::{
-    Parser,
+    ModalResult, Parser,
     ascii::{dec_uint, space0},
-    combinator::{delimited, repeat, separated, terminated},
+    combinator::{cut_err, delimited, repeat, separated, terminated},
+    error::ContextError,
     token::one_of,
 };
 
 #[derive(Debug)]
-struct Machine {
-    target_lights: Vec<bool>,
-    buttons: Vec<Vec<usize>>,
-    joltage: Vec<u32>,
+pub struct Machine {
+    pub target_lights: Vec<bool>,
+    /// The indices of the lights, or counters, each button toggles.
+    pub buttons: Vec<Vec<usize>>,
+    pub joltage: Vec<u32>,
 }
 
 impl Machine {
-    fn parse(input: &str) -> winnow::Result<Self> {
-        fn lights(input: &mut &str) -> winnow::Result<Vec<bool>> {
-            delimited('[', repeat(1.., one_of(['.', '#'])), ']')
-                .parse_next(input)
-                .map(|it: Vec<char>| it.iter().map(|c| *c == '#').collect())
+    /// Parses one line, such as `[.##.] (3) (1,3) {3,5,4,7}`.
+    pub fn parse(input: &str) -> Result<Self, winnow::error::ParseError<&str, ContextError>> {
+        fn lights(input: &mut &str) -> ModalResult<Vec<bool>> {
+            delimited(
+                '['.context(expected("`[`")),
+                repeat(1.., one_of(['.', '#']).context(expected("`.` or `#`"))),
+                cut_err(']'.context(expected("`]`"))),
+            )
+            .parse_next(input)
+            .map(|it: Vec<char>| it.iter().map(|c| *c == '#').collect())
         }
 
-        fn button(input: &mut &str) -> winnow::Result<Vec<usize>> {
+        fn button(input: &mut &str) -> ModalResult<Vec<usize>> {
             terminated(
                 delimited(
-                    '(',
-                    separated(1.., dec_uint::<_, usize, _>, (',', space0)),
-                    ')',
+                    '('.context(expected("`(`")),
+                    cut_err(separated(
+                        1..,
+                        dec_uint::<_, usize, _>.context(expected("a number")),
+                        (',', space0),
+                    )),
+                    cut_err(')'.context(expected("`)`"))),
                 ),
                 space0,
             )
             .parse_next(input)
         }
 
-        fn joltage(input: &mut &str) -> winnow::Result<Vec<u32>> {
+        fn joltage(input: &mut &str) -> ModalResult<Vec<u32>> {
             delimited(
-                '{',
-                separated(1.., dec_uint::<_, u32, _>, (',', space0)),
-                '}',
+                '{'.context(expected("`{`")),
+                cut_err(separated(
+                    1..,
+                    dec_uint::<_, u32, _>.context(expected("a number")),
+                    (',', space0),
+                )),
+                cut_err('}'.context(expected("`}`"))),
             )
             .parse_next(input)
         }
 
         let (target_lights, _, buttons, _, joltage) =
-            (lights, space0, repeat(1.., button), space0, joltage)
-                .parse_peek(input)
-                .map(|(_, result)| result)?;
+            (lights, space0, repeat(1.., button), space0, joltage).parse(input)?;
 
         Ok(Self {
             target_lights,
@@ -55,7 +71,7 @@ impl Machine {
         })
     }
 
-    fn lights_fewest_presses(&self) -> usize {
+    pub fn lights_fewest_presses(&self) -> usize {
         self.buttons
             .iter()
             .powerset()
@@ -77,28 +93,45 @@ impl Machine {
             .unwrap()
     }
 
-    fn joltage_fewest_presses(&self) -> usize {
+    pub fn joltage_fewest_presses(&self) -> usize {
         todo!("Linear programming!")
     }
 }
 
-type ParsedInput = Vec<Machine>;
+pub type ParsedInput = Vec<Machine>;
+
+pub struct Day10;
+
+impl Day for Day10 {
+    const DAY: u8 = 10;
+    type Parsed = ParsedInput;
+    type Answer = usize;
 
-#[aoc_generator(day10)]
-fn input_generator(input: &str) -> ParsedInput {
+    fn parse(input: &str) -> Result<ParsedInput, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(input: &ParsedInput) -> usize {
+        solve_part1(input)
+    }
+
+    fn part2(input: &ParsedInput) -> usize {
+        solve_part2(input)
+    }
+}
+
+pub fn input_generator(input: &str) -> Result<ParsedInput, ParseError> {
     input
         .lines()
-        .map(|line| Machine::parse(line).unwrap())
+        .map(|line| Machine::parse(line).map_err(|e| ParseError::winnow(10, input, &e)))
         .collect()
 }
 
-#[aoc(day10, part1)]
-fn solve_part1(input: &ParsedInput) -> usize {
+pub fn solve_part1(input: &ParsedInput) -> usize {
     input.iter().map(Machine::lights_fewest_presses).sum()
 }
 
-#[aoc(day10, part2)]
-fn solve_part2(input: &ParsedInput) -> usize {
+pub fn solve_part2(input: &ParsedInput) -> usize {
     input.iter().map(Machine::joltage_fewest_presses).sum()
 }
 
@@ -118,6 +151,19 @@ mod tests {
         assert_eq!(machine.lights_fewest_presses(), expected);
     }
 
+    #[rstest]
+    #[case("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7} (4)", (1, 49, "end of input"))]
+    #[case("[.#x.] (3) {3,5,4,7}", (1, 4, "`]`"))]
+    #[case("[.##.] (3) (1,3\n", (1, 16, "`)`"))]
+    fn test_day10_input_generator_error(
+        #[case] input: &str,
+        #[case] (line, column, expected): (usize, usize, &str),
+    ) {
+        let error = input_generator(input).unwrap_err();
+        assert_eq!((error.line, error.column), (line, column));
+        assert_eq!(error.expected, expected);
+    }
+
     #[rstest]
     #[case("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}", 10)]
     #[case("[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}", 12)]
@@ -125,24 +171,6 @@ mod tests {
     fn test_day10_machine_joltage_fewest_presses(#[case] input: &str, #[case] expected: usize) {
         let machine = Machine::parse(input).unwrap();
         dbg!(&machine);
-        assert_eq!(machine.lights_fewest_presses(), expected);
-    }
-
-    #[test]
-    fn test_day10_part1() {
-        let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
-[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
-[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
-        let parsed = input_generator(input);
-        assert_eq!(solve_part1(&parsed), 7);
-    }
-
-    #[test]
-    fn test_day10_part2() {
-        let input = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
-[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
-[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
-        let parsed = input_generator(input);
-        assert_eq!(solve_part2(&parsed), 33);
+        assert_eq!(machine.joltage_fewest_presses(), expected);
     }
 }
@@ -1,22 +1,67 @@
 use std::collections::HashMap;
 
-use aoc_runner_derive::{aoc, aoc_generator};
-use cached::proc_macro::cached;
+use aoc_common::{Day, Variant, assume::Assumption, cancel, parse::ParseError};
 use petgraph::graph::NodeIndex;
 
-type Graph = petgraph::graph::Graph<String, ()>;
+pub type Graph = petgraph::graph::Graph<String, ()>;
 
-struct ParsedInput {
-    graph: Graph,
-    you: Option<NodeIndex>,
-    out: Option<NodeIndex>,
-    svr: Option<NodeIndex>,
-    dac: Option<NodeIndex>,
-    fft: Option<NodeIndex>,
+/// The devices and their outputs, along with the ones the puzzle names.
+pub struct ParsedInput {
+    pub graph: Graph,
+    pub you: Option<NodeIndex>,
+    pub out: Option<NodeIndex>,
+    pub svr: Option<NodeIndex>,
+    pub dac: Option<NodeIndex>,
+    pub fft: Option<NodeIndex>,
 }
 
-#[aoc_generator(day11)]
-fn input_generator(input: &str) -> ParsedInput {
+pub struct Day11;
+
+impl Day for Day11 {
+    const DAY: u8 = 11;
+    type Parsed = ParsedInput;
+    type Answer = u64;
+
+    const PART1_NAME: Option<&'static str> = Some("rec");
+
+    fn parse(input: &str) -> Result<ParsedInput, ParseError> {
+        input_generator(input)
+    }
+
+    fn part1(pi: &ParsedInput) -> u64 {
+        solve_part1_rec(pi).into()
+    }
+
+    fn part2(pi: &ParsedInput) -> u64 {
+        solve_part2(pi)
+    }
+
+    fn variants() -> &'static [Variant<Self>] {
+        &[
+            Variant {
+                part: 1,
+                name: "rec_cached",
+                solve: |pi| solve_part1_rec_cached(pi).into(),
+            },
+            Variant {
+                part: 1,
+                name: "basic",
+                solve: |pi| solve_part1_basic(pi).into(),
+            },
+        ]
+    }
+
+    fn assumptions(pi: &ParsedInput) -> Vec<Assumption> {
+        vec![Assumption::unless(
+            "the graph has no cycles",
+            petgraph::algo::toposort(&pi.graph, None)
+                .err()
+                .map(|cycle| format!("`{}` is on a cycle", pi.graph[cycle.node_id()])),
+        )]
+    }
+}
+
+pub fn input_generator(input: &str) -> Result<ParsedInput, ParseError> {
     let mut graph = Graph::new();
     let mut nodes = HashMap::new();
     let mut you = None;
@@ -26,8 +71,9 @@ fn input_generator(input: &str) -> ParsedInput {
     let mut fft = None;
 
     for line in input.lines() {
-        let mut split = line.split(": ");
-        let node = split.next().unwrap();
+        let (node, outputs) = line
+            .split_once(": ")
+            .ok_or_else(|| ParseError::at(11, input, &line[line.len()..], "`: `"))?;
         let node_idx = *nodes
             .entry(node)
             .or_insert_with(|| graph.add_node(node.to_owned()));
@@ -45,7 +91,7 @@ fn input_generator(input: &str) -> ParsedInput {
             fft = Some(node_idx);
         }
 
-        for other in split.next().unwrap().split_whitespace() {
+        for other in outputs.split_whitespace() {
             let other_idx = *nodes
                 .entry(other)
                 .or_insert_with(|| graph.add_node(other.to_owned()));
@@ -58,23 +104,17 @@ fn input_generator(input: &str) -> ParsedInput {
         }
     }
 
-    assert!(
-        !petgraph::algo::is_cyclic_directed(&graph),
-        "Graph is cyclic!"
-    );
-
-    ParsedInput {
+    Ok(ParsedInput {
         graph,
         you,
         out,
         svr,
         dac,
         fft,
-    }
+    })
 }
 
-#[aoc(day11, part1, rec)]
-fn solve_part1_rec(pi: &ParsedInput) -> u32 {
+pub fn solve_part1_rec(pi: &ParsedInput) -> u32 {
     fn paths_to(graph: &Graph, node: NodeIndex, target: NodeIndex) -> u32 {
         let mut count = 0;
         for neighbour in graph.neighbors(node) {
@@ -94,18 +134,25 @@ fn solve_part1_rec(pi: &ParsedInput) -> u32 {
     paths_to(graph, start, target)
 }
 
-#[aoc(day11, part1, rec_cached)]
-fn solve_part1_rec_cached(pi: &ParsedInput) -> u32 {
-    #[cached(key = "(NodeIndex)", convert = "{ (node) }")]
-    fn paths_to(graph: &Graph, node: NodeIndex, target: NodeIndex) -> u32 {
+pub fn solve_part1_rec_cached(pi: &ParsedInput) -> u32 {
+    fn paths_to(
+        graph: &Graph,
+        node: NodeIndex,
+        target: NodeIndex,
+        memo: &mut HashMap<NodeIndex, u32>,
+    ) -> u32 {
+        if let Some(&count) = memo.get(&node) {
+            return count;
+        }
         let mut count = 0;
         for neighbour in graph.neighbors(node) {
             if neighbour == target {
                 count += 1;
             } else {
-                count += paths_to(graph, neighbour, target);
+                count += paths_to(graph, neighbour, target, memo);
             }
         }
+        memo.insert(node, count);
         count
     }
 
@@ -113,11 +160,10 @@ fn solve_part1_rec_cached(pi: &ParsedInput) -> u32 {
     let start = pi.you.unwrap();
     let target = pi.out.unwrap();
 
-    paths_to(graph, start, target)
+    paths_to(graph, start, target, &mut HashMap::new())
 }
 
-#[aoc(day11, part1, basic)]
-fn solve_part1_basic(pi: &ParsedInput) -> u32 {
+pub fn solve_part1_basic(pi: &ParsedInput) -> u32 {
     let graph = &pi.graph;
     let start = pi.you.unwrap();
     let target = pi.out.unwrap();
@@ -126,6 +172,7 @@ fn solve_part1_basic(pi: &ParsedInput) -> u32 {
     let mut stack = vec![start];
 
     while let Some(node) = stack.pop() {
+        cancel::checkpoint();
         for neighbour in graph.neighbors(node) {
             if neighbour == target {
                 paths += 1;
@@ -138,85 +185,69 @@ fn solve_part1_basic(pi: &ParsedInput) -> u32 {
     paths
 }
 
-#[aoc(day11, part2)]
-fn solve_part2(pi: &ParsedInput) -> u64 {
-    #[rustfmt::skip]
-    #[cached(key = "(NodeIndex, bool, bool)", convert = "{ (node, visited_dac, visited_fft) }")]
-    fn paths_to(graph: &Graph, node: NodeIndex, target: NodeIndex, dac: NodeIndex, fft: NodeIndex, visited_dac: bool, visited_fft: bool) -> u64 {
-        let mut count = 0;
-        for neighbour in graph.neighbors(node) {
-            if neighbour == target {
-                if visited_dac && visited_fft {
-                    count += 1;
+pub fn solve_part2(pi: &ParsedInput) -> u64 {
+    struct Search<'a> {
+        graph: &'a Graph,
+        target: NodeIndex,
+        dac: NodeIndex,
+        fft: NodeIndex,
+        memo: HashMap<(NodeIndex, bool, bool), u64>,
+    }
+
+    impl Search<'_> {
+        fn paths_to(&mut self, node: NodeIndex, visited_dac: bool, visited_fft: bool) -> u64 {
+            let key = (node, visited_dac, visited_fft);
+            if let Some(&count) = self.memo.get(&key) {
+                return count;
+            }
+            let mut count = 0;
+            for neighbour in self.graph.neighbors(node) {
+                if neighbour == self.target {
+                    if visited_dac && visited_fft {
+                        count += 1;
+                    }
+                } else {
+                    count += self.paths_to(
+                        neighbour,
+                        visited_dac || neighbour == self.dac,
+                        visited_fft || neighbour == self.fft,
+                    );
                 }
-            } else {
-                count += paths_to(graph, neighbour, target, dac, fft, visited_dac || neighbour == dac, visited_fft || neighbour == fft);
             }
+            self.memo.insert(key, count);
+            count
         }
-        count
     }
 
-    let graph = &pi.graph;
-    let svr = pi.svr.unwrap();
-    let dac = pi.dac.unwrap();
-    let fft = pi.fft.unwrap();
-    let target = pi.out.unwrap();
-
-    paths_to(graph, svr, target, dac, fft, false, false)
+    let mut search = Search {
+        graph: &pi.graph,
+        target: pi.out.unwrap(),
+        dac: pi.dac.unwrap(),
+        fft: pi.fft.unwrap(),
+        memo: HashMap::new(),
+    };
+    search.paths_to(pi.svr.unwrap(), false, false)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// The memos must not leak from one input into the next, as they would if
+    /// they were global and keyed by node index alone.
     #[test]
-    fn test_day11_part1_rec() {
-        let input = "aaa: you hhh
-you: bbb ccc
-bbb: ddd eee
-ccc: ddd eee fff
-ddd: ggg
-eee: out
-fff: out
-ggg: out
-hhh: ccc fff iii
-iii: out";
-        let parsed = input_generator(input);
-        assert_eq!(solve_part1_rec(&parsed), 5);
-    }
-
-    #[test]
-    fn test_day11_part1_basic() {
-        let input = "aaa: you hhh
-you: bbb ccc
-bbb: ddd eee
-ccc: ddd eee fff
-ddd: ggg
-eee: out
-fff: out
-ggg: out
-hhh: ccc fff iii
-iii: out";
-        let parsed = input_generator(input);
-        assert_eq!(solve_part1_basic(&parsed), 5);
-    }
+    fn test_day11_inputs_in_sequence() {
+        let first = input_generator("you: a b\na: out\nb: out").unwrap();
+        let second = input_generator("you: a b\na: out\nb: a out").unwrap();
+        for (pi, expected) in [(&first, 2), (&second, 3)] {
+            assert_eq!(solve_part1_rec(pi), expected);
+            assert_eq!(solve_part1_rec_cached(pi), expected);
+            assert_eq!(solve_part1_basic(pi), expected);
+        }
 
-    #[test]
-    fn test_day11_part2() {
-        let input = "svr: aaa bbb
-aaa: fft
-fft: ccc
-bbb: tty
-tty: ccc
-ccc: ddd eee
-ddd: hub
-hub: fff
-eee: dac
-dac: fff
-fff: ggg hhh
-ggg: out
-hhh: out";
-        let parsed = input_generator(input);
-        assert_eq!(solve_part2(&parsed), 2);
+        let first = input_generator("svr: dac\ndac: fft\nfft: out").unwrap();
+        let second = input_generator("svr: dac x\ndac: fft\nx: dac\nfft: out").unwrap();
+        assert_eq!(solve_part2(&first), 1);
+        assert_eq!(solve_part2(&second), 2);
     }
 }
@@ -1,15 +1,29 @@
-use aoc_runner_derive::aoc_lib;
+//! Advent of Code 2025, one public module per day with its parsed types,
+//! generator and solvers, plus a type implementing [`aoc_common::Day`].
 
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
+#[cfg(feature = "day01")]
+pub mod day01;
+#[cfg(feature = "day02")]
+pub mod day02;
+#[cfg(feature = "day03")]
+pub mod day03;
+#[cfg(feature = "day04")]
+pub mod day04;
+#[cfg(feature = "day05")]
+pub mod day05;
+#[cfg(feature = "day06")]
+pub mod day06;
+#[cfg(feature = "day07")]
+pub mod day07;
+#[cfg(feature = "day08")]
+pub mod day08;
+#[cfg(feature = "day09")]
+pub mod day09;
+#[cfg(feature = "day10")]
+pub mod day10;
+#[cfg(feature = "day11")]
+pub mod day11;
 
-aoc_lib! { year = 2025 }
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+aoc_common::example_tests!();
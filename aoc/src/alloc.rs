@@ -0,0 +1,164 @@
+//! Heap accounting for the `alloc-stats` feature: a global allocator that
+//! counts what passes through it, so each phase of a solver can be measured.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Allocations made while a closure ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    /// Highest number of live bytes reached above what was live beforehand.
+    pub peak: usize,
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+/// Runs `f`, returning its allocation statistics when the tracking allocator
+/// is compiled in. Counters are global, so other threads allocating at the
+/// same time are counted too.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    #[cfg(feature = "alloc-stats")]
+    {
+        let (value, stats) = tracking::measure(f);
+        (value, Some(stats))
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        (f(), None)
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod tracking {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicUsize, Ordering::Relaxed},
+    };
+
+    use super::AllocStats;
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    static BYTES: AtomicUsize = AtomicUsize::new(0);
+    static LIVE: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    struct Tracking;
+
+    #[global_allocator]
+    static GLOBAL: Tracking = Tracking;
+
+    fn grow(size: usize) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        BYTES.fetch_add(size, Relaxed);
+        let live = LIVE.fetch_add(size, Relaxed) + size;
+        PEAK.fetch_max(live, Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for Tracking {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc(layout) };
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = unsafe { System.alloc_zeroed(layout) };
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) };
+            LIVE.fetch_sub(layout.size(), Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+            if !new_ptr.is_null() {
+                LIVE.fetch_sub(layout.size(), Relaxed);
+                grow(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+        let allocations = ALLOCATIONS.load(Relaxed);
+        let bytes = BYTES.load(Relaxed);
+        let live = LIVE.load(Relaxed);
+        PEAK.store(live, Relaxed);
+
+        let value = f();
+
+        let stats = AllocStats {
+            allocations: ALLOCATIONS.load(Relaxed) - allocations,
+            bytes: BYTES.load(Relaxed) - bytes,
+            peak: PEAK.load(Relaxed).saturating_sub(live),
+        };
+        (value, stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(0, "0 B")]
+    #[case(1000, "1000 B")]
+    #[case(1536, "1.5 KiB")]
+    #[case(3 * 1024 * 1024, "3.0 MiB")]
+    fn test_format_bytes(#[case] bytes: usize, #[case] expected: &str) {
+        assert_eq!(format_bytes(bytes), expected);
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn test_measure_counts_allocations() {
+        let (_, stats) = measure(|| {
+            let mut rows = Vec::new();
+            for _ in 0..10 {
+                rows.push(vec![0u8; 1000]);
+            }
+            drop(rows);
+        });
+        let stats = stats.unwrap();
+        assert!(stats.allocations >= 11);
+        assert!(stats.bytes >= 10_000);
+        assert!(stats.peak >= 10_000);
+    }
+}
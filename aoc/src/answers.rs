@@ -0,0 +1,110 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+
+/// Accepted answers for one year, kept in `answers.toml` next to the year's
+/// `Cargo.toml`:
+///
+/// ```toml
+/// [day1]
+/// part1 = "969"
+/// part2 = "5887"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+impl Answers {
+    pub fn path(root: &str) -> PathBuf {
+        Path::new(root).join("answers.toml")
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        if path.exists() {
+            Self::parse(&fs::read_to_string(path)?)
+                .with_context(|| format!("invalid answers {}", path.display()))
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let table: BTreeMap<String, BTreeMap<String, String>> = toml::from_str(content)?;
+        let mut answers = BTreeMap::new();
+        for (day, parts) in table {
+            let Some(day) = day.strip_prefix("day").and_then(|d| d.parse().ok()) else {
+                bail!("expected a `dayN` table, found `{day}`");
+            };
+            for (part, answer) in parts {
+                let Some(part) = part.strip_prefix("part").and_then(|p| p.parse().ok()) else {
+                    bail!("expected a `partN` key, found `{part}`");
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: &str) {
+        self.answers.insert((day, part), answer.to_owned());
+    }
+}
+
+// written by hand so days come out in numeric order
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut current_day = None;
+        for (&(day, part), answer) in &self.answers {
+            if current_day != Some(day) {
+                if current_day.is_some() {
+                    f.write_char('\n')?;
+                }
+                writeln!(f, "[day{day}]")?;
+                current_day = Some(day);
+            }
+            writeln!(f, "part{part} = {}", toml::Value::from(answer.as_str()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers_roundtrip() {
+        let mut answers = Answers::default();
+        answers.set(10, 1, "7");
+        answers.set(2, 2, "44487518055");
+        answers.set(2, 1, "1227775554");
+
+        let written = answers.to_string();
+        assert_eq!(
+            written,
+            "[day2]\npart1 = \"1227775554\"\npart2 = \"44487518055\"\n\n[day10]\npart1 = \"7\"\n"
+        );
+        assert_eq!(Answers::parse(&written).unwrap(), answers);
+    }
+
+    #[test]
+    fn test_answers_invalid_key() {
+        assert!(Answers::parse("[first]\npart1 = \"1\"").is_err());
+        assert!(Answers::parse("[day1]\none = \"1\"").is_err());
+    }
+}
@@ -0,0 +1,420 @@
+use std::{
+    fs,
+    hint::black_box,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, bail};
+use aoc_common::{Prepared, Solver, Year};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    isolate::{self, Failure, Stage},
+    run, table,
+};
+
+#[derive(Args)]
+pub struct Options {
+    /// Untimed runs before sampling starts
+    #[arg(long, default_value_t = 3)]
+    warmup: u32,
+    /// Timed runs per generator and solver
+    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..))]
+    samples: u32,
+    /// Stop sampling a target after this many seconds, keeping at least two samples
+    #[arg(long, default_value_t = 5.0)]
+    budget: f64,
+    /// Write the results as JSON, usable later as a baseline
+    #[arg(long)]
+    export: Option<PathBuf>,
+    /// Compare against results previously written with `--export`
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+    /// Percentage by which the median may grow before it counts as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub samples: usize,
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub stddev_ns: f64,
+    pub min_ns: f64,
+    pub max_ns: f64,
+    /// Samples outside the Tukey fences (1.5 IQR beyond the quartiles).
+    pub outliers: usize,
+}
+
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let (low, high) = (position.floor() as usize, position.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (position - low as f64)
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut ns = samples
+            .iter()
+            .map(|d| d.as_nanos() as f64)
+            .collect::<Vec<_>>();
+        ns.sort_unstable_by(f64::total_cmp);
+
+        let n = ns.len() as f64;
+        let mean = ns.iter().sum::<f64>() / n;
+        let variance = if ns.len() > 1 {
+            ns.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let (q1, q3) = (quantile(&ns, 0.25), quantile(&ns, 0.75));
+        let fence = 1.5 * (q3 - q1);
+
+        Self {
+            samples: ns.len(),
+            mean_ns: mean,
+            median_ns: quantile(&ns, 0.5),
+            stddev_ns: variance.sqrt(),
+            min_ns: ns[0],
+            max_ns: ns[ns.len() - 1],
+            outliers: ns
+                .iter()
+                .filter(|&&x| x < q1 - fence || x > q3 + fence)
+                .count(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Generator,
+    Solver,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: u8,
+    pub part: Option<u8>,
+    pub variant: Option<String>,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+impl Record {
+    fn key(&self) -> (u32, u8, Option<u8>, Option<&str>, Phase) {
+        let variant = self.variant.as_deref();
+        (self.year, self.day, self.part, variant, self.phase)
+    }
+
+    fn label(&self) -> String {
+        label(self.year, self.day, self.part, self.variant.as_deref())
+    }
+}
+
+fn label(year: u32, day: u8, part: Option<u8>, variant: Option<&str>) -> String {
+    let mut label = format!("{year} Day {day}");
+    match part {
+        Some(part) => label += &format!(" - Part {part}"),
+        None => label += " - generator",
+    }
+    if let Some(variant) = variant {
+        label += &format!(" - {variant}");
+    }
+    label
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Report {
+    pub records: Vec<Record>,
+}
+
+/// Relative change of the median against the baseline, in percent.
+fn change(record: &Record, baseline: &Report) -> Option<f64> {
+    let previous = baseline.records.iter().find(|r| r.key() == record.key())?;
+    Some((record.stats.median_ns / previous.stats.median_ns - 1.0) * 100.0)
+}
+
+fn sample(options: &Options, mut f: impl FnMut() -> Result<(), Failure>) -> Result<Stats, Failure> {
+    for _ in 0..options.warmup {
+        f()?;
+    }
+
+    let budget = Duration::from_secs_f64(options.budget);
+    let start = Instant::now();
+    let mut samples = Vec::new();
+    while samples.len() < options.samples as usize
+        && (samples.len() < 2 || start.elapsed() < budget)
+    {
+        let sample_start = Instant::now();
+        f()?;
+        samples.push(sample_start.elapsed());
+    }
+    Ok(Stats::from_samples(&samples))
+}
+
+/// Builds the solver's input, catching panics.
+fn prepare(solver: &Solver, input: &str) -> Result<Box<dyn Prepared>, Failure> {
+    run::checked(isolate::catch(|| solver.prepare(input)), Stage::Generator)
+}
+
+fn format_ns(ns: f64) -> String {
+    format!("{:.2?}", Duration::from_nanos(ns as u64))
+}
+
+/// The table row of `record`, counting it in `regressions` if it is one.
+fn row(
+    record: &Record,
+    baseline: &Report,
+    options: &Options,
+    regressions: &mut usize,
+) -> Vec<String> {
+    let stats = &record.stats;
+    let change = change(record, baseline);
+    let flag = match change {
+        Some(change) if change > options.threshold => {
+            *regressions += 1;
+            "REGRESSION"
+        }
+        Some(change) if change < -options.threshold => "faster",
+        _ => "",
+    };
+    vec![
+        record.label(),
+        format_ns(stats.mean_ns),
+        format_ns(stats.median_ns),
+        format_ns(stats.stddev_ns),
+        format!("{}/{}", stats.outliers, stats.samples),
+        change.map_or_else(String::new, |change| format!("{change:+.1}%")),
+        flag.to_owned(),
+    ]
+}
+
+/// The row of a target without measurements.
+fn flagged(label: String, flag: &str) -> Vec<String> {
+    let mut row = vec![String::new(); 7];
+    row[0] = label;
+    row[6] = flag.to_owned();
+    row
+}
+
+pub fn bench(solvers: &[(&Year, &Solver)], input: &run::Input, options: &Options) -> Result<()> {
+    let baseline = match &options.baseline {
+        Some(path) => serde_json::from_str(
+            &fs::read_to_string(path)
+                .with_context(|| format!("could not read baseline {}", path.display()))?,
+        )?,
+        None => Report::default(),
+    };
+
+    let mut report = Report::default();
+    let mut rows = Vec::new();
+    let mut regressions = 0;
+    let mut failures = 0;
+    let mut failed = |label: String, failure: Failure| {
+        failures += 1;
+        eprintln!("{label}: {failure}\n");
+        flagged(label, "FAILED")
+    };
+    for day in solvers.chunk_by(|(y1, s1), (y2, s2)| (y1.year, s1.day) == (y2.year, s2.day)) {
+        let (year, first) = day[0];
+        // a missing input skips its day without losing what was measured so far
+        let input = match input.read(year, first.day) {
+            Ok(input) => input,
+            Err(e) => {
+                let label = label(year.year, first.day, None, None);
+                eprintln!("{label}: SKIPPED, {e}\n");
+                rows.push(flagged(label, "NO INPUT"));
+                continue;
+            }
+        };
+
+        if let Err(failure) = run::check_input(year, first.day, &input) {
+            rows.push(failed(label(year.year, first.day, None, None), failure));
+            continue;
+        }
+
+        // every solver of a day shares its generator, so any of them builds the same input
+        let stats = sample(options, || {
+            prepare(first, &input).map(|p| drop(black_box(p)))
+        });
+        let record = match stats {
+            Ok(stats) => Record {
+                year: year.year,
+                day: first.day,
+                part: None,
+                variant: None,
+                phase: Phase::Generator,
+                stats,
+            },
+            Err(failure) => {
+                // the solvers cannot run without their input
+                rows.push(failed(label(year.year, first.day, None, None), failure));
+                continue;
+            }
+        };
+        rows.push(row(&record, &baseline, options, &mut regressions));
+        report.records.push(record);
+
+        for &(_, solver) in day {
+            let stats = prepare(solver, &input).and_then(|prepared| {
+                sample(options, || {
+                    isolate::catch(|| black_box(prepared.run()))
+                        .map(drop)
+                        .map_err(|message| Failure::Panic {
+                            stage: Stage::Solver,
+                            message,
+                        })
+                })
+            });
+            let stats = match stats {
+                Ok(stats) => stats,
+                Err(failure) => {
+                    let label = label(year.year, solver.day, Some(solver.part), solver.name);
+                    rows.push(failed(label, failure));
+                    continue;
+                }
+            };
+            let record = Record {
+                year: year.year,
+                day: solver.day,
+                part: Some(solver.part),
+                variant: solver.name.map(str::to_owned),
+                phase: Phase::Solver,
+                stats,
+            };
+            rows.push(row(&record, &baseline, options, &mut regressions));
+            report.records.push(record);
+        }
+    }
+
+    print!(
+        "{}",
+        table::render(
+            &[
+                "target",
+                "mean",
+                "median",
+                "stddev",
+                "outliers",
+                "vs baseline",
+                ""
+            ],
+            &rows
+        )
+    );
+
+    if let Some(path) = &options.export {
+        fs::write(path, serde_json::to_string_pretty(&report)?)?;
+        println!("\nResults written to {}", path.display());
+    }
+    if failures > 0 {
+        bail!("{failures} target(s) failed");
+    }
+    if regressions > 0 {
+        bail!(
+            "{regressions} target(s) regressed by more than {}%",
+            options.threshold
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn durations(ns: &[u64]) -> Vec<Duration> {
+        ns.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn test_stats_from_samples() {
+        let stats = Stats::from_samples(&durations(&[10, 12, 11, 13, 12, 11, 100]));
+        assert_eq!(stats.samples, 7);
+        assert_eq!(stats.median_ns, 12.0);
+        assert_eq!(stats.min_ns, 10.0);
+        assert_eq!(stats.max_ns, 100.0);
+        assert_eq!(stats.outliers, 1);
+        assert!((stats.mean_ns - 169.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_single_sample() {
+        let stats = Stats::from_samples(&durations(&[42]));
+        assert_eq!(stats.median_ns, 42.0);
+        assert_eq!(stats.stddev_ns, 0.0);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    fn options(samples: u32) -> Options {
+        Options {
+            warmup: 1,
+            samples,
+            budget: 5.0,
+            export: None,
+            baseline: None,
+            threshold: 10.0,
+        }
+    }
+
+    #[test]
+    fn test_sample_stops_at_failure() {
+        let mut runs = 0;
+        let failure = sample(&options(30), || {
+            runs += 1;
+            isolate::catch(|| assert!(runs < 3, "boom")).map_err(|message| Failure::Panic {
+                stage: Stage::Solver,
+                message,
+            })
+        })
+        .unwrap_err();
+        assert_eq!(runs, 3);
+        assert!(
+            failure
+                .to_string()
+                .starts_with("PANICKED while running: boom")
+        );
+    }
+
+    fn record(variant: Option<&str>, median_ns: f64) -> Record {
+        Record {
+            year: 2025,
+            day: 4,
+            part: Some(2),
+            variant: variant.map(str::to_owned),
+            phase: Phase::Solver,
+            stats: Stats {
+                median_ns,
+                ..Stats::from_samples(&durations(&[1]))
+            },
+        }
+    }
+
+    #[test]
+    fn test_change_against_baseline() {
+        let baseline = Report {
+            records: vec![record(None, 200.0), record(Some("simple"), 100.0)],
+        };
+        assert_eq!(change(&record(None, 250.0), &baseline), Some(25.0));
+        assert_eq!(
+            change(&record(Some("simple"), 50.0), &baseline),
+            Some(-50.0)
+        );
+        assert_eq!(change(&record(Some("other"), 50.0), &baseline), None);
+    }
+
+    #[test]
+    fn test_report_roundtrip() {
+        let report = Report {
+            records: vec![record(Some("simple"), 100.0)],
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: Report = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.records, report.records);
+    }
+}
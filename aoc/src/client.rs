@@ -0,0 +1,58 @@
+use anyhow::{Context, Result};
+use ureq::Agent;
+
+use crate::config::Config;
+
+const USER_AGENT: &str = concat!(
+    "github.com/SpacewaIker/aoc ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// Authenticated access to the Advent of Code website.
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let config = Agent::config_builder().user_agent(USER_AGENT).build();
+        Self {
+            agent: Agent::new_with_config(config),
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Ok(Self::new(config.base_url(), config.session()?))
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn input(&self, year: u32, day: u8) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        self.agent
+            .get(&url)
+            .header("Cookie", self.cookie())
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .with_context(|| format!("could not download {url}"))
+    }
+
+    pub fn answer(&self, year: u32, day: u8, part: u8, answer: &str) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let level = part.to_string();
+        self.agent
+            .post(&url)
+            .header("Cookie", self.cookie())
+            .send_form([("level", level.as_str()), ("answer", answer)])
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .with_context(|| format!("could not submit to {url}"))
+    }
+}
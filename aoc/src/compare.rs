@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, bail};
+use aoc_common::{Solver, Year};
+
+use crate::{run, table};
+
+/// Picks the answer most variants agree on; ties go to the first one seen.
+pub fn consensus<'a>(answers: &[Option<&'a str>]) -> Option<&'a str> {
+    let mut counts = HashMap::new();
+    for answer in answers.iter().flatten() {
+        *counts.entry(*answer).or_insert(0) += 1;
+    }
+    let max = counts.values().copied().max()?;
+    answers.iter().flatten().copied().find(|a| counts[a] == max)
+}
+
+/// Runs every variant of each selected (day, part) on the same input and
+/// reports whether they agree.
+pub fn compare(
+    solvers: &[(&Year, &Solver)],
+    input: &run::Input,
+    options: &run::Options,
+) -> Result<()> {
+    let groups = solvers
+        .chunk_by(|(y1, s1), (y2, s2)| (y1.year, s1.day, s1.part) == (y2.year, s2.day, s2.part))
+        .filter(|group| group.len() > 1)
+        .collect::<Vec<_>>();
+    if groups.is_empty() {
+        bail!("no selected day has more than one variant per part");
+    }
+
+    let mut disagreements = 0;
+    for group in groups {
+        let (year, first) = group[0];
+        // a missing input skips its day, as in `verify`
+        let input = match input.read(year, first.day) {
+            Ok(input) => input,
+            Err(e) => {
+                println!(
+                    "{} Day {} - Part {}: SKIPPED, {e}\n",
+                    year.year, first.day, first.part
+                );
+                continue;
+            }
+        };
+        let solutions = group
+            .iter()
+            .map(|(_, solver)| run::solve(year, solver, &input, options))
+            .collect::<Vec<_>>();
+        let answers = solutions
+            .iter()
+            .map(|s| s.as_ref().ok().map(|s| s.answer.as_str()))
+            .collect::<Vec<_>>();
+        let expected = consensus(&answers);
+
+        let rows = group
+            .iter()
+            .zip(&solutions)
+            .map(|((_, solver), solution)| {
+                let name = solver.name.unwrap_or("(default)").to_owned();
+                match solution {
+                    Ok(s) => vec![
+                        name,
+                        s.answer.clone(),
+                        format!("{:.2?}", s.generator),
+                        format!("{:.2?}", s.runner),
+                        if Some(s.answer.as_str()) == expected {
+                            ""
+                        } else {
+                            "DIFFERS"
+                        }
+                        .to_owned(),
+                    ],
+                    Err(_) => vec![
+                        name,
+                        "-".to_owned(),
+                        "-".to_owned(),
+                        "-".to_owned(),
+                        "FAILED".to_owned(),
+                    ],
+                }
+            })
+            .collect::<Vec<_>>();
+
+        println!("{} Day {} - Part {}", year.year, first.day, first.part);
+        print!(
+            "{}",
+            table::render(&["variant", "answer", "generator", "runner", ""], &rows)
+        );
+        println!();
+
+        for ((_, solver), solution) in group.iter().zip(&solutions) {
+            if let Err(failure) = solution {
+                eprintln!("{solver}: {failure}\n");
+            }
+        }
+        if answers.iter().any(|a| *a != expected) {
+            disagreements += 1;
+        }
+    }
+
+    if disagreements > 0 {
+        bail!("variants disagree on {disagreements} part(s)");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(&[Some("1"), Some("1"), Some("2")], Some("1"))]
+    #[case(&[Some("2"), Some("1"), Some("1")], Some("1"))]
+    #[case(&[Some("2"), Some("1")], Some("2"))]
+    #[case(&[None, Some("3")], Some("3"))]
+    #[case(&[None, None], None)]
+    fn test_consensus(#[case] answers: &[Option<&str>], #[case] expected: Option<&str>) {
+        assert_eq!(consensus(answers), expected);
+    }
+}
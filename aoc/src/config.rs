@@ -0,0 +1,80 @@
+use std::{env, fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Settings for talking to adventofcode.com, read from `aoc.toml` at the
+/// workspace root (or `$AOC_CONFIG`). `AOC_SESSION` and `AOC_BASE_URL`
+/// override the file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    session: Option<String>,
+    base_url: Option<String>,
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        let path = env::var_os("AOC_CONFIG").map_or_else(default_path, PathBuf::from);
+        let mut config = if path.exists() {
+            Self::parse(&fs::read_to_string(&path)?)
+                .with_context(|| format!("invalid config {}", path.display()))?
+        } else {
+            Self::default()
+        };
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = Some(base_url);
+        }
+        Ok(config)
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    pub fn session(&self) -> Result<&str> {
+        self.session
+            .as_deref()
+            .map(str::trim)
+            .filter(|session| !session.is_empty())
+            .context("no session token: set AOC_SESSION or `session` in aoc.toml")
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+    }
+}
+
+fn default_path() -> PathBuf {
+    crate::workspace_root().join("aoc.toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_parse() {
+        let config =
+            Config::parse("session = \"53616c746564\"\nbase_url = \"http://127.0.0.1:8080/\"")
+                .unwrap();
+        assert_eq!(config.session().unwrap(), "53616c746564");
+        assert_eq!(config.base_url(), "http://127.0.0.1:8080");
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config = Config::parse("").unwrap();
+        assert!(config.session().is_err());
+        assert_eq!(config.base_url(), DEFAULT_BASE_URL);
+    }
+}
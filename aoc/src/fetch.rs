@@ -0,0 +1,91 @@
+use std::{fs, path::Path};
+
+use anyhow::Result;
+
+use crate::client::Client;
+
+/// Whether `path` holds an input; `aoc new` leaves empty placeholders behind.
+pub fn is_cached(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+/// Downloads the input for a puzzle into `path`, unless it is already there.
+/// Returns whether a download happened.
+pub fn fetch(client: &Client, year: u32, day: u8, path: &Path) -> Result<bool> {
+    if is_cached(path) {
+        return Ok(false);
+    }
+
+    let input = client.input(year, day)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, input)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stub::Stub;
+
+    use super::*;
+
+    #[test]
+    fn test_fetch_downloads_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input/2025/day3.txt");
+        let stub = Stub::serve(vec![(200, "987654321111111\n")]);
+
+        let client = Client::new(&stub.url, "cafe");
+        assert!(fetch(&client, 2025, 3, &path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "987654321111111\n");
+
+        let requests = stub.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2025/day/3/input");
+        assert_eq!(requests[0].header("cookie"), Some("session=cafe"));
+        assert!(
+            requests[0]
+                .header("user-agent")
+                .is_some_and(|agent| agent.contains("github.com/SpacewaIker/aoc"))
+        );
+    }
+
+    #[test]
+    fn test_fetch_uses_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("day3.txt");
+        fs::write(&path, "cached").unwrap();
+        let stub = Stub::serve(vec![]);
+
+        let client = Client::new(&stub.url, "cafe");
+        assert!(!fetch(&client, 2025, 3, &path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "cached");
+        assert!(stub.requests().is_empty());
+    }
+
+    #[test]
+    fn test_fetch_replaces_placeholder() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("day3.txt");
+        fs::write(&path, "").unwrap();
+        let stub = Stub::serve(vec![(200, "818181911112111\n")]);
+
+        let client = Client::new(&stub.url, "cafe");
+        assert!(fetch(&client, 2025, 3, &path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "818181911112111\n");
+    }
+
+    #[test]
+    fn test_fetch_error_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("day3.txt");
+        let stub = Stub::serve(vec![(400, "Puzzle inputs differ by user.")]);
+
+        let client = Client::new(&stub.url, "expired");
+        assert!(fetch(&client, 2025, 3, &path).is_err());
+        assert!(!path.exists());
+        assert_eq!(stub.requests().len(), 1);
+    }
+}
@@ -0,0 +1,235 @@
+//! Machine-readable output for `aoc run --format`.
+
+use std::{fmt::Write, time::Duration};
+
+use aoc_common::{Solver, Year};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{isolate::Failure, run::Solution, table};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Progress as it happens, for people
+    #[default]
+    Text,
+    /// An array of records
+    Json,
+    /// One line per record, with a header
+    Csv,
+    /// A table, for the README
+    Markdown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    Failed,
+    Panicked,
+    Aborted,
+    TimedOut,
+    /// The input breaks one of the day's assumptions.
+    Skipped,
+    /// The input could not be read.
+    NoInput,
+}
+
+impl Status {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Failed => "failed",
+            Self::Panicked => "panicked",
+            Self::Aborted => "aborted",
+            Self::TimedOut => "timed_out",
+            Self::Skipped => "skipped",
+            Self::NoInput => "no_input",
+        }
+    }
+}
+
+impl From<&Failure> for Status {
+    fn from(failure: &Failure) -> Self {
+        match failure {
+            Failure::Error { .. } => Self::Failed,
+            Failure::Panic { .. } => Self::Panicked,
+            Failure::Abort(_) => Self::Aborted,
+            Failure::Timeout(_) => Self::TimedOut,
+            Failure::Skipped(_) => Self::Skipped,
+        }
+    }
+}
+
+/// The outcome of one solver on its input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: u8,
+    pub part: u8,
+    pub variant: Option<String>,
+    pub answer: Option<String>,
+    pub generator_ns: Option<u128>,
+    pub solver_ns: Option<u128>,
+    pub status: Status,
+    pub error: Option<String>,
+}
+
+impl Record {
+    pub fn new(year: &Year, solver: &Solver, result: Result<&Solution, (Status, &str)>) -> Self {
+        let (solution, status, error) = match result {
+            Ok(solution) => (Some(solution), Status::Ok, None),
+            Err((status, error)) => (None, status, Some(error.to_owned())),
+        };
+        Self {
+            year: year.year,
+            day: solver.day,
+            part: solver.part,
+            variant: solver.name.map(str::to_owned),
+            answer: solution.map(|s| s.answer.clone()),
+            generator_ns: solution.map(|s| s.generator.as_nanos()),
+            solver_ns: solution.map(|s| s.runner.as_nanos()),
+            status,
+            error,
+        }
+    }
+}
+
+// quoted only when needed, as in RFC 4180
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn csv(records: &[Record]) -> String {
+    let mut out = "year,day,part,variant,answer,generator_ns,solver_ns,status,error\n".to_owned();
+    for r in records {
+        let fields = [
+            r.year.to_string(),
+            r.day.to_string(),
+            r.part.to_string(),
+            r.variant.clone().unwrap_or_default(),
+            r.answer.clone().unwrap_or_default(),
+            r.generator_ns.map(|ns| ns.to_string()).unwrap_or_default(),
+            r.solver_ns.map(|ns| ns.to_string()).unwrap_or_default(),
+            r.status.name().to_owned(),
+            r.error.clone().unwrap_or_default(),
+        ];
+        let fields = fields.iter().map(|f| csv_field(f)).collect::<Vec<_>>();
+        writeln!(out, "{}", fields.join(",")).unwrap();
+    }
+    out
+}
+
+fn markdown(records: &[Record]) -> String {
+    let duration = |ns: Option<u128>| {
+        ns.map_or_else(String::new, |ns| {
+            format!("{:.2?}", Duration::from_nanos(ns as u64))
+        })
+    };
+    let headers = [
+        "Year",
+        "Day",
+        "Part",
+        "Variant",
+        "Answer",
+        "Generator",
+        "Solver",
+        "Status",
+    ];
+    let rows = records
+        .iter()
+        .map(|r| {
+            vec![
+                r.year.to_string(),
+                r.day.to_string(),
+                r.part.to_string(),
+                r.variant.clone().unwrap_or_default(),
+                r.answer
+                    .as_deref()
+                    .map_or_else(String::new, |a| format!("`{a}`")),
+                duration(r.generator_ns),
+                duration(r.solver_ns),
+                r.status.name().to_owned(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    table::markdown(&headers, &rows)
+}
+
+impl Format {
+    pub fn render(self, records: &[Record]) -> String {
+        match self {
+            Self::Text => unreachable!("text is printed as the solvers run"),
+            Self::Json => serde_json::to_string_pretty(records).unwrap() + "\n",
+            Self::Csv => csv(records),
+            Self::Markdown => markdown(records),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                year: 2025,
+                day: 11,
+                part: 1,
+                variant: Some("rec".to_owned()),
+                answer: Some("708".to_owned()),
+                generator_ns: Some(280_186),
+                solver_ns: Some(11_263),
+                status: Status::Ok,
+                error: None,
+            },
+            Record {
+                year: 2025,
+                day: 10,
+                part: 2,
+                variant: None,
+                answer: None,
+                generator_ns: None,
+                solver_ns: None,
+                status: Status::Panicked,
+                error: Some("PANICKED while running: not yet implemented, at day10.rs".to_owned()),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            Format::Csv.render(&records()),
+            "year,day,part,variant,answer,generator_ns,solver_ns,status,error\n\
+             2025,11,1,rec,708,280186,11263,ok,\n\
+             2025,10,2,,,,,panicked,\"PANICKED while running: not yet implemented, at day10.rs\"\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        assert_eq!(
+            Format::Markdown.render(&records()),
+            "| Year | Day | Part | Variant | Answer | Generator | Solver  | Status   |\n\
+             | ---- | --- | ---- | ------- | ------ | --------- | ------- | -------- |\n\
+             | 2025 | 11  | 1    | rec     | `708`  | 280.19µs  | 11.26µs | ok       |\n\
+             | 2025 | 10  | 2    |         |        |           |         | panicked |\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&Format::Json.render(&records())).unwrap();
+        assert_eq!(json[0]["status"], "ok");
+        assert_eq!(json[0]["solver_ns"], 11_263);
+        assert_eq!(json[1]["status"], "panicked");
+        assert!(json[1]["answer"].is_null());
+    }
+}
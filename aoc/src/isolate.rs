@@ -0,0 +1,312 @@
+//! Keeps one broken generator or solver from taking the whole run down.
+//!
+//! Panics are caught in-process. Aborts such as stack overflows cannot be, so
+//! `--isolate` runs each solver in a child `aoc worker` process instead and
+//! reports how it died. With `--timeout`, solvers run on a watched thread and
+//! are cancelled through [`aoc_common::cancel`] once their time is up.
+
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    env, fmt,
+    io::{self, Read, Write},
+    panic::{self, AssertUnwindSafe},
+    process::{Command, Stdio},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use aoc_common::{
+    Solver, Year,
+    cancel::{Cancelled, Token},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::run::{self, Solution};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stage {
+    Generator,
+    Solver,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Generator => write!(f, "generating"),
+            Self::Solver => write!(f, "running"),
+        }
+    }
+}
+
+/// Why a solver produced no answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Failure {
+    Error {
+        stage: Stage,
+        message: String,
+    },
+    Panic {
+        stage: Stage,
+        message: String,
+    },
+    /// The worker process died without reporting back.
+    Abort(String),
+    Timeout(Duration),
+    /// The input breaks these assumptions of the day, so it was not solved.
+    Skipped(Vec<String>),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error { stage, message } => write!(f, "FAILED while {stage}:\n{message}"),
+            Self::Panic { stage, message } => write!(f, "PANICKED while {stage}: {message}"),
+            Self::Abort(message) => write!(f, "ABORTED: {message}"),
+            Self::Timeout(timeout) => write!(f, "TIMED OUT after {timeout:?}"),
+            Self::Skipped(broken) => {
+                write!(f, "SKIPPED, the input breaks:\n\t{}", broken.join("\n\t"))
+            }
+        }
+    }
+}
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Silences the default panic message for panics [`catch`] handles, keeping
+/// the message and location for the report instead.
+pub fn install_hook() {
+    let default = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if CATCHING.get() {
+            let message = info.payload_as_str().unwrap_or("Box<dyn Any>");
+            let message = match info.location() {
+                Some(location) => format!("{message} at {location}"),
+                None => message.to_owned(),
+            };
+            PANIC.set(Some(message));
+        } else {
+            default(info);
+        }
+    }));
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else if payload.is::<Cancelled>() {
+        "cancelled".to_owned()
+    } else {
+        "Box<dyn Any>".to_owned()
+    }
+}
+
+/// Runs `f`, turning a panic into its message.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+    result.map_err(|payload| PANIC.take().unwrap_or_else(|| payload_message(&*payload)))
+}
+
+/// How long a cancelled solver gets to reach a checkpoint before it is
+/// abandoned.
+const GRACE: Duration = Duration::from_secs(1);
+
+/// Solvers normally run on the main thread, keep as much stack for them.
+const STACK_SIZE: usize = 8 << 20;
+
+/// Runs `f` on its own thread and gives up on it after `timeout`. A solver
+/// that does not reach a checkpoint within the grace period keeps running in
+/// the background, still cancelled, until it reaches one.
+pub fn with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    f: impl FnOnce() -> Result<T, Failure> + Send + 'static,
+) -> Result<T, Failure> {
+    let (sender, receiver) = mpsc::channel();
+    let token = Token::new();
+    let solver_token = token.clone();
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            solver_token.install();
+            sender.send(f())
+        })
+        .map_err(|e| Failure::Abort(format!("could not start solver thread: {e}")))?;
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            let _ = receiver.recv_timeout(GRACE);
+            Err(Failure::Timeout(timeout))
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(Failure::Abort("solver thread died".to_owned())),
+    }
+}
+
+/// Runs `solver` in an `aoc worker` child process fed `input` on stdin. The
+/// worker enforces `timeout` itself and is killed if it does not report back
+/// shortly after.
+pub fn in_child(
+    year: &Year,
+    solver: &Solver,
+    input: &str,
+    timeout: Option<Duration>,
+) -> Result<Solution, Failure> {
+    let abort = |e: io::Error| Failure::Abort(format!("could not run worker: {e}"));
+
+    let mut command = Command::new(env::current_exe().map_err(abort)?);
+    command.args(["worker", "--year", &year.year.to_string()]);
+    command.args(["--day", &solver.day.to_string()]);
+    command.args(["--part", &solver.part.to_string()]);
+    if let Some(name) = solver.name {
+        command.args(["--variant", name]);
+    }
+    if let Some(timeout) = timeout {
+        command.args(["--timeout", &timeout.as_secs_f64().to_string()]);
+    }
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(abort)?;
+    // the worker reads all of stdin before producing any output
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .map_err(abort)?;
+
+    let drain = |mut pipe: Box<dyn Read + Send>| {
+        thread::spawn(move || {
+            let mut content = Vec::new();
+            let _ = pipe.read_to_end(&mut content);
+            String::from_utf8_lossy(&content).into_owned()
+        })
+    };
+    let stdout = drain(Box::new(child.stdout.take().unwrap()));
+    let stderr = drain(Box::new(child.stderr.take().unwrap()));
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout + 2 * GRACE);
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(abort)? {
+            break status;
+        }
+        if deadline.is_some_and(|deadline| Instant::now() > deadline) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Failure::Timeout(timeout.unwrap()));
+        }
+        thread::sleep(Duration::from_millis(5));
+    };
+    let (stdout, stderr) = (stdout.join().unwrap(), stderr.join().unwrap());
+
+    // the report is the last line, anything before it was printed by the solver
+    let (printed, report) = stdout
+        .trim_end()
+        .rsplit_once('\n')
+        .unwrap_or(("", stdout.trim_end()));
+    if !printed.is_empty() {
+        println!("{printed}");
+    }
+    if let Ok(result) = serde_json::from_str(report) {
+        return result;
+    }
+
+    Err(Failure::Abort(match stderr.trim().lines().last() {
+        Some(line) => format!("{status}, {line}"),
+        None => status.to_string(),
+    }))
+}
+
+/// The child side of [`in_child`].
+pub fn worker(solver: &Solver, timeout: Option<Duration>) -> Result<()> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    let result = run::solve_here(solver, &input, timeout);
+    println!("{}", serde_json::to_string(&result)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::cancel;
+
+    use super::*;
+
+    #[test]
+    fn test_catch() {
+        assert_eq!(catch(|| 42), Ok(42));
+        assert_eq!(
+            catch(|| -> u32 { panic!("not yet implemented: {}", "part 2") }),
+            Err("not yet implemented: part 2".to_owned())
+        );
+        assert_eq!(catch(|| -> u32 { panic!("boom") }), Err("boom".to_owned()));
+    }
+
+    #[test]
+    fn test_with_timeout() {
+        assert_eq!(with_timeout(Duration::from_secs(5), || Ok(42)), Ok(42));
+
+        let start = Instant::now();
+        let result = with_timeout(Duration::from_millis(50), || {
+            catch(|| {
+                loop {
+                    cancel::checkpoint();
+                    thread::sleep(Duration::from_millis(1));
+                }
+            })
+            .map_err(|message| Failure::Panic {
+                stage: Stage::Solver,
+                message,
+            })
+        });
+        assert_eq!(
+            result,
+            Err::<(), _>(Failure::Timeout(Duration::from_millis(50)))
+        );
+        assert!(start.elapsed() < GRACE);
+        assert_eq!(
+            Failure::Timeout(Duration::from_secs(10)).to_string(),
+            "TIMED OUT after 10s"
+        );
+    }
+
+    #[test]
+    fn test_with_timeout_keeps_abandoned_solver_cancelled() {
+        let (sender, receiver) = mpsc::channel();
+        let result = with_timeout(Duration::from_millis(20), move || {
+            // outlasts the grace period without reaching a checkpoint
+            thread::sleep(GRACE + Duration::from_millis(100));
+            sender.send(cancel::is_cancelled()).unwrap();
+            Ok(())
+        });
+        assert_eq!(result, Err(Failure::Timeout(Duration::from_millis(20))));
+        assert_eq!(
+            with_timeout(Duration::from_secs(5), || Ok(cancel::is_cancelled())),
+            Ok(false)
+        );
+        assert!(receiver.recv().unwrap());
+    }
+
+    #[test]
+    fn test_failure_roundtrip() {
+        let failure = Failure::Panic {
+            stage: Stage::Solver,
+            message: "boom at 2025/src/day10.rs:82:9".to_owned(),
+        };
+        let json = serde_json::to_string(&Err::<Solution, _>(&failure)).unwrap();
+        let parsed: Result<Solution, Failure> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.unwrap_err(), failure);
+    }
+}
@@ -0,0 +1,338 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use aoc_common::{Solver, Year};
+use clap::{Args, Parser, Subcommand};
+
+use crate::{
+    answers::Answers,
+    client::Client,
+    config::Config,
+    format::Format,
+    scaffold::Template,
+    submit::{History, Outcome},
+};
+
+mod alloc;
+mod answers;
+mod bench;
+mod client;
+mod compare;
+mod config;
+mod fetch;
+mod format;
+mod isolate;
+mod matrix;
+mod report;
+mod run;
+mod scaffold;
+#[cfg(test)]
+mod stub;
+mod submit;
+mod table;
+mod verify;
+
+static YEARS: &[&Year] = &[&aoc2015::REGISTRY, &aoc2025::REGISTRY];
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code solutions across all years")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the selected solvers on their puzzle input
+    Run {
+        #[command(flatten)]
+        selection: Selection,
+        #[command(flatten)]
+        source: run::Source,
+        /// Run on every input set, `input/<year>/dayN.txt` and `input/<year>/<account>/dayN.txt`
+        #[arg(long, conflicts_with = "input")]
+        all_inputs: bool,
+        /// How to print the results
+        #[arg(long, value_enum, default_value_t, conflicts_with = "all_inputs")]
+        format: Format,
+        #[command(flatten)]
+        options: run::Options,
+    },
+    /// List the selected solvers without running them
+    List(Selection),
+    /// Run every variant of the selected parts and check that they agree
+    Compare {
+        #[command(flatten)]
+        selection: Selection,
+        #[command(flatten)]
+        source: run::Source,
+        #[command(flatten)]
+        options: run::Options,
+    },
+    /// Benchmark the selected generators and solvers (build with --release)
+    Bench {
+        #[command(flatten)]
+        selection: Selection,
+        #[command(flatten)]
+        source: run::Source,
+        #[command(flatten)]
+        options: bench::Options,
+    },
+    /// Download a puzzle input into the year's input directory
+    Fetch(Puzzle),
+    /// Run a solver and submit its answer
+    Submit {
+        #[command(flatten)]
+        target: Target,
+        /// Save the answer to `answers.toml` if it is accepted
+        #[arg(long)]
+        record: bool,
+        #[command(flatten)]
+        options: run::Options,
+    },
+    /// Check the selected solvers against the recorded answers
+    Verify {
+        #[command(flatten)]
+        selection: Selection,
+        #[command(flatten)]
+        options: run::Options,
+    },
+    /// Write a progress report with stars, timings and what is left to do
+    Report {
+        #[arg(long)]
+        year: Option<u32>,
+        /// Directory to write `README.md` and `index.html` to
+        #[arg(long, default_value = "report")]
+        out: PathBuf,
+        /// Answers and timings written by `aoc run --format json`, solvers missing from it are run
+        #[arg(long)]
+        results: Option<PathBuf>,
+        /// Timings written by `aoc bench --export`, used instead of single runs
+        #[arg(long)]
+        bench: Option<PathBuf>,
+        #[command(flatten)]
+        options: run::Options,
+    },
+    /// Scaffold a new day, creating the year's crate first if needed
+    New {
+        #[command(flatten)]
+        puzzle: Puzzle,
+        #[arg(long, value_enum, default_value_t = Template::Lines)]
+        template: Template,
+    },
+    /// Run one solver on stdin and report back as JSON, for `--isolate`
+    #[command(hide = true)]
+    Worker {
+        #[command(flatten)]
+        target: Target,
+        #[arg(long)]
+        timeout: Option<f64>,
+    },
+}
+
+/// A single day of a single year.
+#[derive(Args)]
+struct Puzzle {
+    #[arg(long)]
+    year: u32,
+    #[arg(long)]
+    day: u8,
+}
+
+impl Puzzle {
+    fn year(&self) -> Result<&'static Year> {
+        find_year(self.year)
+    }
+}
+
+/// One part of a puzzle, optionally pinned to a named solver variant.
+#[derive(Args)]
+struct Target {
+    #[command(flatten)]
+    puzzle: Puzzle,
+    #[arg(long)]
+    part: u8,
+    #[arg(long)]
+    variant: Option<String>,
+}
+
+impl Target {
+    fn solver(&self) -> Result<&'static Solver> {
+        let year = self.puzzle.year()?;
+        year.solvers
+            .iter()
+            .filter(|s| s.day == self.puzzle.day && s.part == self.part)
+            .find(|s| self.variant.is_none() || s.name == self.variant.as_deref())
+            .context("no solver matches the selection")
+    }
+}
+
+fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+fn find_year(year: u32) -> Result<&'static Year> {
+    match YEARS.iter().find(|y| y.year == year) {
+        Some(year) => Ok(year),
+        None => bail!("no solutions for year {year}"),
+    }
+}
+
+/// Narrows down which solvers a command applies to; omitted filters match everything.
+#[derive(Args)]
+struct Selection {
+    #[arg(long)]
+    year: Option<u32>,
+    #[arg(long)]
+    day: Option<u8>,
+    #[arg(long)]
+    part: Option<u8>,
+}
+
+impl Selection {
+    fn solvers(&self) -> Result<Vec<(&'static Year, &'static Solver)>> {
+        if let Some(year) = self.year {
+            find_year(year)?;
+        }
+
+        let selected = YEARS
+            .iter()
+            .filter(|year| self.year.is_none_or(|y| y == year.year))
+            .flat_map(|year| year.solvers.iter().map(move |solver| (*year, solver)))
+            .filter(|(_, solver)| self.day.is_none_or(|d| d == solver.day))
+            .filter(|(_, solver)| self.part.is_none_or(|p| p == solver.part))
+            .collect::<Vec<_>>();
+
+        if selected.is_empty() {
+            bail!("no solver matches the selection");
+        }
+        Ok(selected)
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    isolate::install_hook();
+
+    match cli.command {
+        Command::Run {
+            selection,
+            source,
+            all_inputs,
+            format,
+            options,
+        } => {
+            let solvers = selection.solvers()?;
+            if all_inputs {
+                matrix::matrix(&solvers, &options)
+            } else {
+                run::run(&solvers, &source.load(&solvers)?, &options, format)
+            }
+        }
+        Command::List(selection) => {
+            for (year, solver) in selection.solvers()? {
+                println!("{} {solver}", year.year);
+            }
+            Ok(())
+        }
+        Command::Compare {
+            selection,
+            source,
+            options,
+        } => {
+            let solvers = selection.solvers()?;
+            compare::compare(&solvers, &source.load(&solvers)?, &options)
+        }
+        Command::Bench {
+            selection,
+            source,
+            options,
+        } => {
+            let solvers = selection.solvers()?;
+            bench::bench(&solvers, &source.load(&solvers)?, &options)
+        }
+        Command::Fetch(puzzle) => {
+            let path = puzzle.year()?.input_path(puzzle.day);
+            if fetch::is_cached(&path) {
+                println!("Already cached at {}", path.display());
+                return Ok(());
+            }
+            let client = Client::from_config(&Config::load()?)?;
+            fetch::fetch(&client, puzzle.year, puzzle.day, &path)?;
+            println!("Downloaded {}", path.display());
+            Ok(())
+        }
+        Command::Submit {
+            target,
+            record,
+            options,
+        } => {
+            let year = target.puzzle.year()?;
+            let solver = target.solver()?;
+            let input = run::read_input(year, solver.day)?;
+            let solution = run::solve(year, solver, &input, &options)
+                .map_err(|failure| anyhow!("{solver}: {failure}"))?;
+            println!("{solver}: {}", solution.answer);
+
+            let client = Client::from_config(&Config::load()?)?;
+            let path = History::path(year.root);
+            let mut history = History::load(&path)?;
+            let outcome = submit::submit(
+                &client,
+                &mut history,
+                year.year,
+                (solver.day, solver.part),
+                &solution.answer,
+            )?;
+            history.save(&path)?;
+            println!("{outcome}");
+
+            if record && outcome == Outcome::Correct {
+                let path = Answers::path(year.root);
+                let mut answers = Answers::load(&path)?;
+                answers.set(solver.day, solver.part, &solution.answer);
+                answers.save(&path)?;
+                println!("Recorded in {}", path.display());
+            }
+            Ok(())
+        }
+        Command::Verify { selection, options } => verify::verify(&selection.solvers()?, &options),
+        Command::Report {
+            year,
+            out,
+            results,
+            bench,
+            options,
+        } => {
+            let years = match year {
+                Some(year) => vec![find_year(year)?],
+                None => YEARS.to_vec(),
+            };
+            let stored = report::Stored::load(results.as_deref(), bench.as_deref())?;
+            let reports = years
+                .into_iter()
+                .map(|year| report::collect(year, &stored, &options))
+                .collect::<Result<Vec<_>>>()?;
+            report::write(&reports, &out)?;
+            println!("Report written to {}", out.display());
+            Ok(())
+        }
+        Command::New { puzzle, template } => {
+            let root = workspace_root().join(puzzle.year.to_string());
+            if !root.exists() {
+                scaffold::new_year(workspace_root(), puzzle.year)?;
+                println!("Created crate aoc{} in {}", puzzle.year, root.display());
+            }
+            for path in scaffold::new_day(workspace_root(), puzzle.year, puzzle.day, template)? {
+                println!("Created {}", path.display());
+            }
+            Ok(())
+        }
+        Command::Worker { target, timeout } => {
+            isolate::worker(target.solver()?, timeout.map(Duration::from_secs_f64))
+        }
+    }
+}
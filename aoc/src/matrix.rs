@@ -0,0 +1,204 @@
+//! Runs every solver on every input set, so a solution that only works on
+//! one account's input stands out.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{Context, Result, bail};
+use aoc_common::{Solver, Year};
+
+use crate::{compare, run, table};
+
+/// The set read from `input/<year>/dayN.txt` itself, in parentheses so that
+/// no account directory is mistaken for it.
+const TOP_LEVEL: &str = "(top-level)";
+
+/// Every input set of `dir` with an input for `day`: the top-level one in
+/// `dir/dayN.txt` and one per account in `dir/<account>/dayN.txt`.
+pub fn input_sets(dir: &Path, day: u8) -> Vec<(String, PathBuf)> {
+    let file_name = format!("day{day}.txt");
+    let mut sets = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| {
+            let account = entry.file_name().to_string_lossy().into_owned();
+            (account, entry.path().join(&file_name))
+        })
+        .filter(|(_, path)| path.is_file())
+        .collect::<Vec<_>>();
+    sets.sort();
+    let top_level = dir.join(&file_name);
+    if top_level.is_file() {
+        sets.insert(0, (TOP_LEVEL.to_owned(), top_level));
+    }
+    sets
+}
+
+/// A cell of the matrix: the answer and total time, or why there is none.
+type Outcome = Result<(String, Duration), String>;
+
+fn solve_all(
+    year: &Year,
+    day: &[(&Year, &Solver)],
+    input: &str,
+    options: &run::Options,
+) -> Vec<Outcome> {
+    day.iter()
+        .map(|(_, solver)| {
+            run::solve(year, solver, input, options)
+                .map(|s| (s.answer, s.generator + s.runner))
+                .map_err(|failure| failure.to_string())
+        })
+        .collect()
+}
+
+/// Marks the answers that differ from what the other variants of the same
+/// part give on the same input.
+fn disagreements(day: &[(&Year, &Solver)], outcomes: &[Outcome]) -> Vec<bool> {
+    let mut differs = vec![false; day.len()];
+    let mut start = 0;
+    for part in day.chunk_by(|(_, s1), (_, s2)| s1.part == s2.part) {
+        let range = start..start + part.len();
+        start = range.end;
+        let answers = outcomes[range.clone()]
+            .iter()
+            .map(|o| o.as_ref().ok().map(|(answer, _)| answer.as_str()))
+            .collect::<Vec<_>>();
+        let expected = compare::consensus(&answers);
+        for (differs, answer) in differs[range].iter_mut().zip(&answers) {
+            *differs = answer.is_some() && *answer != expected;
+        }
+    }
+    differs
+}
+
+fn cell(outcome: Option<&(Outcome, bool)>) -> String {
+    match outcome {
+        None => "-".to_owned(),
+        Some((Ok((answer, time)), false)) => format!("{answer} ({time:.2?})"),
+        Some((Ok((answer, time)), true)) => format!("{answer} ({time:.2?}) DIFFERS"),
+        // just the headline, such as `PANICKED while running`
+        Some((Err(message), _)) => message.split([':', ',']).next().unwrap().to_owned(),
+    }
+}
+
+/// Runs every selected solver on every input set and prints the answers
+/// and timings as a table with one column per set.
+pub fn matrix(solvers: &[(&Year, &Solver)], options: &run::Options) -> Result<()> {
+    // for each selected solver, its outcome on each set and whether it disagrees
+    let mut rows = vec![BTreeMap::new(); solvers.len()];
+    let mut start = 0;
+    for day in solvers.chunk_by(|(y1, s1), (y2, s2)| (y1.year, s1.day) == (y2.year, s2.day)) {
+        let (year, first) = day[0];
+        let day_rows = &mut rows[start..start + day.len()];
+        start += day.len();
+
+        let dir = year.input_path(first.day).parent().unwrap().to_owned();
+        for (set, path) in input_sets(&dir, first.day) {
+            let input = fs::read_to_string(&path)
+                .with_context(|| format!("could not read input {}", path.display()))?;
+            let outcomes = solve_all(year, day, &input, options);
+            let differs = disagreements(day, &outcomes);
+            for ((row, outcome), differs) in day_rows.iter_mut().zip(outcomes).zip(differs) {
+                row.insert(set.clone(), (outcome, differs));
+            }
+        }
+    }
+
+    let sets = rows
+        .iter()
+        .flat_map(BTreeMap::keys)
+        .collect::<BTreeSet<_>>();
+    if sets.is_empty() {
+        bail!("no input found for the selected days");
+    }
+    // the top-level set first, accounts in name order
+    let sets = sets
+        .iter()
+        .filter(|set| set.as_str() == TOP_LEVEL)
+        .chain(sets.iter().filter(|set| set.as_str() != TOP_LEVEL))
+        .map(|set| set.as_str())
+        .collect::<Vec<_>>();
+
+    let table_rows = solvers
+        .iter()
+        .zip(&rows)
+        .map(|((year, solver), row)| {
+            let mut cells = vec![format!("{} {solver}", year.year)];
+            cells.extend(sets.iter().map(|set| cell(row.get(*set))));
+            cells
+        })
+        .collect::<Vec<_>>();
+    let mut headers = vec!["solver"];
+    headers.extend(&sets);
+    print!("{}", table::render(&headers, &table_rows));
+
+    let (mut failures, mut differing) = (0, 0);
+    for ((year, solver), row) in solvers.iter().zip(&rows) {
+        for (set, (outcome, differs)) in row {
+            if let Err(message) = outcome {
+                failures += 1;
+                eprintln!("\n{} {solver} on {set}: {message}", year.year);
+            }
+            differing += usize::from(*differs);
+        }
+    }
+    match (failures, differing) {
+        (0, 0) => Ok(()),
+        (failures, 0) => bail!("{failures} run(s) failed"),
+        (0, differing) => bail!("{differing} answer(s) differ from the other variants'"),
+        (failures, differing) => bail!(
+            "{failures} run(s) failed and {differing} answer(s) differ from the other variants'"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_sets() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |path: &str| {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "1\n").unwrap();
+        };
+        write("day3.txt");
+        write("day4.txt");
+        write("zoe/day3.txt");
+        write("alice/day3.txt");
+        write("bob/day4.txt");
+        write("default/day4.txt");
+
+        let names = |day| {
+            input_sets(dir.path(), day)
+                .into_iter()
+                .map(|(set, _)| set)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(3), ["(top-level)", "alice", "zoe"]);
+        assert_eq!(names(4), ["(top-level)", "bob", "default"]);
+        assert!(names(5).is_empty());
+        assert_eq!(
+            input_sets(dir.path(), 3)[1].1,
+            dir.path().join("alice/day3.txt")
+        );
+    }
+
+    #[test]
+    fn test_cell() {
+        let answer = (Ok(("42".to_owned(), Duration::from_millis(3))), false);
+        assert_eq!(cell(Some(&answer)), "42 (3.00ms)");
+        let panic = (Err("PANICKED while running: boom".to_owned()), false);
+        assert_eq!(cell(Some(&panic)), "PANICKED while running");
+        assert_eq!(cell(None), "-");
+    }
+}
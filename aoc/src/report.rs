@@ -0,0 +1,450 @@
+//! `aoc report`: a progress summary per year, written as Markdown and HTML.
+
+use std::{fmt::Write, fs, path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+use aoc_common::{Solver, Year};
+use serde::de::DeserializeOwned;
+
+use crate::{answers::Answers, bench, format, run, table};
+
+/// What is known about one part of one day.
+#[derive(Debug, Default)]
+pub struct Part {
+    /// Whether any of its solvers gives an answer.
+    pub solved: bool,
+    /// An answer was accepted and recorded in `answers.toml`.
+    pub star: bool,
+    /// The quickest variant that gave an answer, and its total time.
+    pub fastest: Option<(Option<&'static str>, Duration)>,
+    /// Why this part needs more work, if it does.
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct YearReport {
+    pub year: u32,
+    /// Indexed by day - 1, then part - 1.
+    pub days: Vec<[Part; 2]>,
+}
+
+impl YearReport {
+    fn stars(&self) -> usize {
+        self.days.iter().flatten().filter(|part| part.star).count()
+    }
+
+    fn total(&self) -> Duration {
+        let fastest = self.days.iter().flatten().filter_map(|part| part.fastest);
+        fastest.map(|(_, time)| time).sum()
+    }
+
+    fn problems(&self) -> impl Iterator<Item = String> + '_ {
+        self.days.iter().enumerate().flat_map(|(idx, parts)| {
+            parts.iter().enumerate().flat_map(move |(part, p)| {
+                p.problems
+                    .iter()
+                    .map(move |problem| format!("Day {} part {}: {problem}", idx + 1, part + 1))
+            })
+        })
+    }
+}
+
+/// Events before 2025 ran for 25 days, later ones for 12.
+fn days_in(year: u32) -> u8 {
+    if year < 2025 { 25 } else { 12 }
+}
+
+fn puzzle_url(year: u32, day: usize) -> String {
+    format!("https://adventofcode.com/{year}/day/{day}")
+}
+
+fn variant(name: Option<&str>, problem: String) -> String {
+    match name {
+        Some(name) => format!("{name} {problem}"),
+        None => problem,
+    }
+}
+
+/// Results written earlier by `aoc run --format json` and `aoc bench --export`,
+/// so the report does not have to solve again.
+#[derive(Debug, Default)]
+pub struct Stored {
+    pub runs: Vec<format::Record>,
+    pub bench: bench::Report,
+}
+
+fn load<T: DeserializeOwned + Default>(path: Option<&Path>) -> Result<T> {
+    let Some(path) = path else {
+        return Ok(T::default());
+    };
+    let content =
+        fs::read_to_string(path).with_context(|| format!("could not read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("could not parse {}", path.display()))
+}
+
+impl Stored {
+    pub fn load(runs: Option<&Path>, bench: Option<&Path>) -> Result<Self> {
+        Ok(Self {
+            runs: load(runs)?,
+            bench: load(bench)?,
+        })
+    }
+
+    /// The recorded answer and time of `solver`, or why it gave none.
+    fn run(&self, year: u32, solver: &Solver) -> Option<Result<(String, Duration), String>> {
+        let record = self.runs.iter().find(|r| {
+            (r.year, r.day, r.part, r.variant.as_deref())
+                == (year, solver.day, solver.part, solver.name)
+        })?;
+        Some(match (record.status, &record.answer) {
+            (format::Status::Ok, Some(answer)) => {
+                let ns =
+                    record.generator_ns.unwrap_or_default() + record.solver_ns.unwrap_or_default();
+                Ok((answer.clone(), Duration::from_nanos(ns as u64)))
+            }
+            _ => Err(record.error.clone().unwrap_or_default()),
+        })
+    }
+
+    /// The benchmarked median of the day's generator plus the one of `solver`.
+    fn bench_time(&self, year: u32, solver: &Solver) -> Option<Duration> {
+        let median = |part, variant| {
+            let record = self.bench.records.iter().find(|r| {
+                (r.year, r.day, r.part, r.variant.as_deref()) == (year, solver.day, part, variant)
+            })?;
+            Some(record.stats.median_ns)
+        };
+        let ns = median(None, None)? + median(Some(solver.part), solver.name)?;
+        Some(Duration::from_nanos(ns as u64))
+    }
+}
+
+/// Collects the report from the stored results, running each solver that
+/// has none once.
+pub fn collect(year: &Year, stored: &Stored, options: &run::Options) -> Result<YearReport> {
+    let answers = Answers::load(&Answers::path(year.root))?;
+    let mut days = Vec::new();
+    for day in 1..=days_in(year.year) {
+        let mut parts = [Part::default(), Part::default()];
+        let started = year.solvers.iter().any(|s| s.day == day);
+        // only read once a solver has no stored result
+        let mut input = None;
+
+        for (idx, report) in parts.iter_mut().enumerate() {
+            let part = idx as u8 + 1;
+            report.star = answers.get(day, part).is_some();
+            if !started {
+                continue;
+            }
+            let solvers = year
+                .solvers
+                .iter()
+                .filter(|s| (s.day, s.part) == (day, part))
+                .collect::<Vec<_>>();
+            for solver in &solvers {
+                let outcome = match stored.run(year.year, solver) {
+                    Some(outcome) => outcome,
+                    None => match input.get_or_insert_with(|| run::read_input(year, day)) {
+                        Ok(input) => run::solve(year, solver, input, options)
+                            .map(|solution| {
+                                let time = solution.generator + solution.runner;
+                                (solution.answer, time)
+                            })
+                            .map_err(|failure| failure.to_string()),
+                        Err(_) => continue,
+                    },
+                };
+                match outcome {
+                    Ok((answer, time)) => {
+                        report.solved = true;
+                        let time = stored.bench_time(year.year, solver).unwrap_or(time);
+                        if report.fastest.is_none_or(|(_, fastest)| time < fastest) {
+                            report.fastest = Some((solver.name, time));
+                        }
+                        if answers
+                            .get(day, part)
+                            .is_some_and(|accepted| accepted != answer)
+                        {
+                            let problem = format!("gives {answer}, not the accepted answer");
+                            report.problems.push(variant(solver.name, problem));
+                        }
+                    }
+                    // just the headline, the rest is for `aoc run`
+                    Err(failure) => {
+                        let headline = failure.lines().next().unwrap_or_default();
+                        report
+                            .problems
+                            .push(variant(solver.name, headline.to_owned()));
+                    }
+                }
+            }
+
+            if solvers.is_empty() {
+                report.problems.push("no solver".to_owned());
+            } else if !year.tested.contains(&(day, part)) {
+                report.problems.push("no example test".to_owned());
+            }
+        }
+        if let Some(Err(e)) = input {
+            parts[0].problems.push(format!("{e:#}"));
+        }
+        days.push(parts);
+    }
+    Ok(YearReport {
+        year: year.year,
+        days,
+    })
+}
+
+/// `★` for an accepted answer, `☆` for an answer that was not recorded.
+fn star(part: &Part) -> &'static str {
+    match (part.star, part.solved) {
+        (true, _) => "★",
+        (false, true) => "☆",
+        (false, false) => "·",
+    }
+}
+
+fn timing(part: &Part) -> String {
+    match part.fastest {
+        Some((Some(variant), time)) => format!("{time:.2?} ({variant})"),
+        Some((None, time)) => format!("{time:.2?}"),
+        None => String::new(),
+    }
+}
+
+fn summary(report: &YearReport) -> String {
+    format!(
+        "{}/{} stars, {:.2?} in total for the fastest variant of each part",
+        report.stars(),
+        2 * report.days.len(),
+        report.total()
+    )
+}
+
+pub fn markdown(reports: &[YearReport]) -> String {
+    let mut out =
+        "# Advent of Code\n\n★ accepted answer, ☆ solved without a recorded answer\n".to_owned();
+    for report in reports {
+        let year = report.year;
+        writeln!(out, "\n## [{year}](https://adventofcode.com/{year})\n").unwrap();
+        writeln!(out, "{}\n", summary(report)).unwrap();
+
+        let rows = report
+            .days
+            .iter()
+            .enumerate()
+            .map(|(idx, [part1, part2])| {
+                vec![
+                    format!("[{}]({})", idx + 1, puzzle_url(year, idx + 1)),
+                    format!("{}{}", star(part1), star(part2)),
+                    timing(part1),
+                    timing(part2),
+                ]
+            })
+            .collect::<Vec<_>>();
+        out += &table::markdown(&["Day", "Stars", "Part 1", "Part 2"], &rows);
+
+        let problems = report.problems().collect::<Vec<_>>();
+        if !problems.is_empty() {
+            writeln!(out, "\n### To do\n").unwrap();
+            for problem in problems {
+                writeln!(out, "- {problem}").unwrap();
+            }
+        }
+    }
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const STYLE: &str = "body { font-family: sans-serif; max-width: 50em; margin: auto; }
+table { border-collapse: collapse; }
+td, th { padding: 0.2em 0.8em; text-align: left; }
+tr:nth-child(even) { background: #f4f4f4; }
+.stars { color: #c90; }";
+
+pub fn html(reports: &[YearReport]) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "<title>Advent of Code</title>\n<style>\n{STYLE}\n</style>"
+    )
+    .unwrap();
+    writeln!(out, "</head>\n<body>\n<h1>Advent of Code</h1>").unwrap();
+    writeln!(
+        out,
+        "<p>★ accepted answer, ☆ solved without a recorded answer</p>"
+    )
+    .unwrap();
+    for report in reports {
+        let year = report.year;
+        writeln!(
+            out,
+            "<h2><a href=\"https://adventofcode.com/{year}\">{year}</a></h2>"
+        )
+        .unwrap();
+        writeln!(out, "<p>{}</p>", summary(report)).unwrap();
+        writeln!(out, "<table>").unwrap();
+        writeln!(
+            out,
+            "<tr><th>Day</th><th>Stars</th><th>Part 1</th><th>Part 2</th></tr>"
+        )
+        .unwrap();
+        for (idx, [part1, part2]) in report.days.iter().enumerate() {
+            writeln!(
+                out,
+                "<tr><td><a href=\"{}\">{}</a></td><td class=\"stars\">{}{}</td><td>{}</td><td>{}</td></tr>",
+                puzzle_url(year, idx + 1),
+                idx + 1,
+                star(part1),
+                star(part2),
+                escape(&timing(part1)),
+                escape(&timing(part2)),
+            )
+            .unwrap();
+        }
+        writeln!(out, "</table>").unwrap();
+
+        let problems = report.problems().collect::<Vec<_>>();
+        if !problems.is_empty() {
+            writeln!(out, "<h3>To do</h3>\n<ul>").unwrap();
+            for problem in problems {
+                writeln!(out, "<li>{}</li>", escape(&problem)).unwrap();
+            }
+            writeln!(out, "</ul>").unwrap();
+        }
+    }
+    writeln!(out, "</body>\n</html>").unwrap();
+    out
+}
+
+/// Writes `README.md` and `index.html` into `dir`.
+pub fn write(reports: &[YearReport], dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("could not create {}", dir.display()))?;
+    fs::write(dir.join("README.md"), markdown(reports))?;
+    fs::write(dir.join("index.html"), html(reports))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> YearReport {
+        let solved = |star, time: u64| Part {
+            solved: true,
+            star,
+            fastest: Some((None, Duration::from_micros(time))),
+            problems: Vec::new(),
+        };
+        YearReport {
+            year: 2025,
+            days: vec![
+                [
+                    solved(true, 20),
+                    Part {
+                        fastest: Some((Some("smart"), Duration::from_micros(30))),
+                        ..solved(true, 0)
+                    },
+                ],
+                [
+                    solved(false, 100),
+                    Part {
+                        problems: vec!["PANICKED while running: <todo>".to_owned()],
+                        ..Part::default()
+                    },
+                ],
+                [Part::default(), Part::default()],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_stored() {
+        let solver = |day, part, name| {
+            let solvers = aoc2025::REGISTRY.solvers.iter();
+            solvers
+                .clone()
+                .find(|s| (s.day, s.part, s.name) == (day, part, name))
+                .unwrap()
+        };
+        let runs = r#"[
+            {"year": 2025, "day": 11, "part": 1, "variant": "rec", "answer": "708", "generator_ns": 280000,
+             "solver_ns": 11000, "status": "ok", "error": null},
+            {"year": 2025, "day": 10, "part": 2, "variant": null, "answer": null, "generator_ns": null,
+             "solver_ns": null, "status": "panicked", "error": "PANICKED while running: <todo>"}
+        ]"#;
+        let bench_record = |part, median_ns| bench::Record {
+            year: 2025,
+            day: 11,
+            part,
+            variant: part.map(|_| "rec".to_owned()),
+            phase: if part.is_some() {
+                bench::Phase::Solver
+            } else {
+                bench::Phase::Generator
+            },
+            stats: bench::Stats {
+                median_ns,
+                ..bench::Stats::from_samples(&[Duration::from_nanos(1)])
+            },
+        };
+        let stored = Stored {
+            runs: serde_json::from_str(runs).unwrap(),
+            bench: bench::Report {
+                records: vec![
+                    bench_record(None, 200_000.0),
+                    bench_record(Some(1), 5_000.0),
+                ],
+            },
+        };
+
+        let rec = solver(11, 1, Some("rec"));
+        assert_eq!(
+            stored.run(2025, rec),
+            Some(Ok(("708".to_owned(), Duration::from_micros(291))))
+        );
+        assert_eq!(
+            stored.run(2025, solver(10, 2, None)),
+            Some(Err("PANICKED while running: <todo>".to_owned()))
+        );
+        assert_eq!(stored.run(2025, solver(11, 1, Some("basic"))), None);
+        assert_eq!(
+            stored.bench_time(2025, rec),
+            Some(Duration::from_micros(205))
+        );
+        assert_eq!(stored.bench_time(2025, solver(11, 2, None)), None);
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = markdown(&[report()]);
+        assert!(markdown.contains("2/6 stars, 150.00µs in total"));
+        assert!(markdown.contains(
+            "| Day                                      | Stars | Part 1   | Part 2          |\n\
+             | ---------------------------------------- | ----- | -------- | --------------- |\n\
+             | [1](https://adventofcode.com/2025/day/1) | ★★    | 20.00µs  | 30.00µs (smart) |\n\
+             | [2](https://adventofcode.com/2025/day/2) | ☆·    | 100.00µs |                 |\n\
+             | [3](https://adventofcode.com/2025/day/3) | ··    |          |                 |\n"
+        ));
+        assert!(markdown.contains("- Day 2 part 2: PANICKED while running: <todo>"));
+    }
+
+    #[test]
+    fn test_html_escapes() {
+        let html = html(&[report()]);
+        assert!(html.contains("<li>Day 2 part 2: PANICKED while running: &lt;todo&gt;</li>"));
+        assert!(!html.contains("<todo>"));
+    }
+}
@@ -0,0 +1,416 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Read, Write},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, bail};
+use aoc_common::{Solver, Year};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    alloc::{self, AllocStats},
+    format::{Format, Record, Status},
+    isolate::{self, Failure, Stage},
+};
+
+#[derive(Args)]
+pub struct Options {
+    /// Run each solver in its own process, so aborts such as stack overflows are reported too
+    #[arg(long)]
+    isolate: bool,
+    /// Give up on a solver after this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<f64>,
+}
+
+impl Options {
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs_f64)
+    }
+}
+
+/// The answer of one solver along with how long each phase took.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Solution {
+    pub answer: String,
+    pub generator: Duration,
+    pub runner: Duration,
+    pub generator_alloc: Option<AllocStats>,
+    pub runner_alloc: Option<AllocStats>,
+}
+
+pub fn read_input(year: &Year, day: u8) -> Result<String> {
+    let path = year.input_path(day);
+    fs::read_to_string(&path).with_context(|| format!("could not read input {}", path.display()))
+}
+
+#[derive(Args)]
+pub struct Source {
+    /// Read the input from this file instead of `input/<year>/dayN.txt`, `-` for stdin
+    #[arg(long, value_name = "PATH")]
+    input: Option<PathBuf>,
+}
+
+impl Source {
+    /// Reads the override up front, stdin can only be read once.
+    pub fn load(&self, solvers: &[(&Year, &Solver)]) -> Result<Input> {
+        let Some(path) = &self.input else {
+            return Ok(Input::Cached);
+        };
+        let days = solvers.chunk_by(|(y1, s1), (y2, s2)| (y1.year, s1.day) == (y2.year, s2.day));
+        if days.count() > 1 {
+            bail!("--input applies to a single day, select one with --year and --day");
+        }
+
+        if path.as_os_str() == "-" {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .context("could not read input from stdin")?;
+            Ok(Input::Given(input))
+        } else {
+            fs::read_to_string(path)
+                .map(Input::Given)
+                .with_context(|| format!("could not read input {}", path.display()))
+        }
+    }
+}
+
+/// The puzzle input for the selected days.
+pub enum Input {
+    /// Each day's file in `input/<year>/`.
+    Cached,
+    /// Given with `--input`, for the one selected day.
+    Given(String),
+}
+
+impl Input {
+    pub fn read(&self, year: &Year, day: u8) -> Result<String> {
+        match self {
+            Self::Cached => read_input(year, day),
+            Self::Given(input) => Ok(input.clone()),
+        }
+    }
+}
+
+pub fn checked<T, E: Display>(
+    result: Result<Result<T, E>, String>,
+    stage: Stage,
+) -> Result<T, Failure> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(Failure::Error {
+            stage,
+            message: e.to_string(),
+        }),
+        Err(message) => Err(Failure::Panic { stage, message }),
+    }
+}
+
+fn measured(solver: &Solver, input: &str) -> Result<Solution, Failure> {
+    let start_time = Instant::now();
+    let (prepared, generator_alloc) = alloc::measure(|| isolate::catch(|| solver.prepare(input)));
+    let prepared = checked(prepared, Stage::Generator)?;
+    let inter_time = Instant::now();
+    let (answer, runner_alloc) = alloc::measure(|| isolate::catch(|| prepared.run()));
+    let answer = answer.map_err(|message| Failure::Panic {
+        stage: Stage::Solver,
+        message,
+    })?;
+    let final_time = Instant::now();
+
+    Ok(Solution {
+        answer: answer.to_string(),
+        generator: inter_time - start_time,
+        runner: final_time - inter_time,
+        generator_alloc,
+        runner_alloc,
+    })
+}
+
+/// Runs one solver in this process, catching panics.
+pub fn solve_here(
+    solver: &Solver,
+    input: &str,
+    timeout: Option<Duration>,
+) -> Result<Solution, Failure> {
+    match timeout {
+        Some(timeout) => {
+            let (solver, input) = (*solver, input.to_owned());
+            isolate::with_timeout(timeout, move || measured(&solver, &input))
+        }
+        None => measured(solver, input),
+    }
+}
+
+/// Fails unless `input` holds to the day's assumptions, which its solvers may
+/// need to finish at all.
+pub fn check_input(year: &Year, day: u8, input: &str) -> Result<(), Failure> {
+    let assumptions =
+        isolate::catch(|| year.assumptions(day, input)).map_err(|message| Failure::Panic {
+            stage: Stage::Generator,
+            message,
+        })?;
+    let broken = assumptions
+        .iter()
+        .flatten()
+        .filter(|a| !a.holds())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if broken.is_empty() {
+        Ok(())
+    } else {
+        Err(Failure::Skipped(broken))
+    }
+}
+
+/// Runs one solver, unless its input breaks the day's assumptions.
+pub fn solve(
+    year: &Year,
+    solver: &Solver,
+    input: &str,
+    options: &Options,
+) -> Result<Solution, Failure> {
+    check_input(year, solver.day, input)?;
+    if options.isolate {
+        isolate::in_child(year, solver, input, options.timeout())
+    } else {
+        solve_here(solver, input, options.timeout())
+    }
+}
+
+/// Checks the day's assumptions about its input, printing each of them to
+/// `out`. Returns how many do not hold.
+fn check_assumptions(year: &Year, day: u8, input: &str, out: &mut dyn Write) -> usize {
+    let assumptions = match isolate::catch(|| year.assumptions(day, input)) {
+        Ok(Some(assumptions)) => assumptions,
+        Ok(None) => return 0,
+        Err(message) => {
+            eprintln!("Day {day} assumptions: PANICKED: {message}\n");
+            return 1;
+        }
+    };
+    let _ = writeln!(out, "Day {day} assumptions:");
+    for assumption in &assumptions {
+        let _ = writeln!(out, "\t{assumption}");
+    }
+    let _ = writeln!(out);
+    assumptions.iter().filter(|a| !a.holds()).count()
+}
+
+/// Runs every selected solver, reporting failures as they happen and
+/// carrying on with the rest. With a `format` other than text, progress
+/// goes to stderr and the records to stdout once every solver is done.
+pub fn run(
+    solvers: &[(&Year, &Solver)],
+    input: &Input,
+    options: &Options,
+    format: Format,
+) -> Result<()> {
+    let text = format == Format::Text;
+    let mut records = Vec::new();
+    let mut current_year = None;
+    let mut failures = 0;
+    for day in solvers.chunk_by(|(y1, s1), (y2, s2)| (y1.year, s1.day) == (y2.year, s2.day)) {
+        let (year, first) = day[0];
+        if text && current_year != Some(year.year) {
+            println!("Advent of code {}", year.year);
+            current_year = Some(year.year);
+        }
+
+        let mut out: Box<dyn Write> = if text {
+            Box::new(io::stdout())
+        } else {
+            Box::new(io::stderr())
+        };
+        let input = match input.read(year, first.day) {
+            Ok(input) => match check_assumptions(year, first.day, &input, &mut out) {
+                0 => Ok(input),
+                broken => Err((
+                    Status::Skipped,
+                    format!("SKIPPED, the input breaks {broken} assumption(s)"),
+                )),
+            },
+            Err(e) => Err((Status::NoInput, e.to_string())),
+        };
+        for &(_, solver) in day {
+            let solution = input.as_ref().map_err(Clone::clone).and_then(|input| {
+                solve(year, solver, input, options)
+                    .map_err(|failure| (Status::from(&failure), failure.to_string()))
+            });
+            match &solution {
+                Ok(solution) if text => {
+                    println!(
+                        "{solver}: {}\n\tgenerator: {:?},\n\trunner: {:?}",
+                        solution.answer, solution.generator, solution.runner
+                    );
+                    if let (Some(generator), Some(runner)) =
+                        (solution.generator_alloc, solution.runner_alloc)
+                    {
+                        println!("\tgenerator heap: {generator},\n\trunner heap: {runner}");
+                    }
+                    println!();
+                }
+                Ok(_) => {}
+                Err((_, e)) => {
+                    failures += 1;
+                    eprintln!("{solver}: {e}\n");
+                }
+            }
+            if !text {
+                let result = solution
+                    .as_ref()
+                    .map_err(|(status, e)| (*status, e.as_str()));
+                records.push(Record::new(year, solver, result));
+            }
+        }
+    }
+
+    if !text {
+        print!("{}", format.render(&records));
+    }
+    if failures > 0 {
+        bail!("{failures} of {} solver(s) failed", solvers.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use aoc_common::{AnyDay, Day, assume::Assumption, parse::ParseError};
+
+    use super::*;
+
+    struct Panics;
+
+    impl Day for Panics {
+        const DAY: u8 = 10;
+        type Parsed = String;
+        type Answer = u64;
+
+        fn parse(input: &str) -> Result<String, ParseError> {
+            match input {
+                "panic" => panic!("Could not parse input"),
+                "error" => Err(ParseError::new(10, input, 0, "`[`")),
+                _ => Ok(input.to_owned()),
+            }
+        }
+
+        fn part1(_input: &String) -> u64 {
+            0
+        }
+
+        fn part2(_input: &String) -> u64 {
+            todo!("Linear programming!")
+        }
+    }
+
+    /// Assumes the input is not `cycle`, which its solvers would never finish.
+    struct Acyclic;
+
+    impl Day for Acyclic {
+        const DAY: u8 = 11;
+        type Parsed = String;
+        type Answer = u64;
+
+        fn parse(input: &str) -> Result<String, ParseError> {
+            Ok(input.to_owned())
+        }
+
+        fn part1(_input: &String) -> u64 {
+            1
+        }
+
+        fn part2(input: &String) -> u64 {
+            Self::part1(input)
+        }
+
+        fn assumptions(input: &String) -> Vec<Assumption> {
+            let cycle = (input == "cycle").then(String::new);
+            vec![Assumption::unless("the graph has no cycles", cycle)]
+        }
+    }
+
+    #[test]
+    fn test_solve_checks_assumptions() {
+        static DAYS: &[&dyn AnyDay] = &[&Acyclic];
+        static YEAR: Year = Year {
+            year: 2025,
+            root: "",
+            days: DAYS,
+            solvers: LazyLock::new(|| Solver::all(DAYS)),
+            tested: &[],
+        };
+        let options = Options {
+            isolate: false,
+            timeout: None,
+        };
+        let solver = &YEAR.solvers[0];
+        assert_eq!(solve(&YEAR, solver, "dag", &options).unwrap().answer, "1");
+        let failure = solve(&YEAR, solver, "cycle", &options).unwrap_err();
+        assert_eq!(
+            failure.to_string(),
+            "SKIPPED, the input breaks:\n\tFAILED: the graph has no cycles"
+        );
+    }
+
+    fn solver() -> Solver {
+        static DAYS: &[&dyn AnyDay] = &[&Panics];
+        Solver::all(DAYS)[1]
+    }
+
+    #[test]
+    fn test_source_load() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "..S..\n.....").unwrap();
+        let source = Source {
+            input: Some(file.path().to_owned()),
+        };
+        let year = &aoc2025::REGISTRY;
+        let day = |day| year.solvers.iter().filter(move |s| s.day == day);
+        let one_day = day(7).map(|s| (year, s)).collect::<Vec<_>>();
+        let two_days = day(6).chain(day(7)).map(|s| (year, s)).collect::<Vec<_>>();
+
+        let input = source.load(&one_day).unwrap();
+        assert_eq!(input.read(year, 7).unwrap(), "..S..\n.....");
+        assert!(source.load(&two_days).is_err());
+    }
+
+    #[test]
+    fn test_solve_catches_solver_panic() {
+        assert_eq!(
+            solve_here(&solver(), "", None).unwrap_err(),
+            Failure::Panic {
+                stage: Stage::Solver,
+                message: "not yet implemented: Linear programming!".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn test_solve_catches_generator_panic() {
+        assert_eq!(
+            solve_here(&solver(), "panic", None).unwrap_err(),
+            Failure::Panic {
+                stage: Stage::Generator,
+                message: "Could not parse input".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn test_solve_reports_generator_error() {
+        assert_eq!(
+            solve_here(&solver(), "error", None)
+                .unwrap_err()
+                .to_string(),
+            "FAILED while generating:\nday 10: expected `[` at line 1, column 1\n  |\n1 | error\n  | ^"
+        );
+    }
+}
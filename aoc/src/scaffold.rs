@@ -0,0 +1,441 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Template {
+    /// One entry per line
+    Lines,
+    /// A character grid
+    Grid,
+    /// `node: neighbour neighbour` adjacency lines
+    Graph,
+}
+
+impl Template {
+    fn source(self) -> &'static str {
+        match self {
+            Self::Lines => include_str!("../templates/day/lines.rs"),
+            Self::Grid => include_str!("../templates/day/grid.rs"),
+            Self::Graph => include_str!("../templates/day/graph.rs"),
+        }
+    }
+}
+
+fn day_number(name: &str) -> Option<&str> {
+    let digits = name.strip_prefix("day")?;
+    (!digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())).then_some(digits)
+}
+
+/// Follows the naming already used in the year: 2015 has `day1.rs`, 2025 has
+/// `day01.rs`. New years get padded names.
+fn module_name<'a>(existing: impl IntoIterator<Item = &'a str>, day: u8) -> String {
+    let digits = existing
+        .into_iter()
+        .filter_map(day_number)
+        .collect::<Vec<_>>();
+    let padded = digits.iter().any(|d| d.starts_with('0')) || !digits.iter().any(|d| d.len() == 1);
+    if padded {
+        format!("day{day:02}")
+    } else {
+        format!("day{day}")
+    }
+}
+
+fn module_day(module: &str) -> Option<u8> {
+    day_number(module)?.parse().ok()
+}
+
+/// Adds `pub mod <module>;` to `lib.rs` behind its feature, keeping the day
+/// modules in order.
+fn insert_mod(lib: &str, module: &str) -> Result<String> {
+    let feature = format!("#[cfg(feature = \"{module}\")]");
+    let declaration = format!("pub mod {module};");
+    let mut lines = lib.lines().collect::<Vec<_>>();
+    // `mod` or `pub mod`
+    fn declared(line: &str) -> Option<&str> {
+        let line = line.strip_prefix("pub ").unwrap_or(line);
+        line.strip_prefix("mod ")?.strip_suffix(';')
+    }
+    if lines.iter().any(|line| declared(line) == Some(module)) {
+        bail!("{module} is already declared in lib.rs");
+    }
+
+    let day = module_day(module).unwrap();
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| declared(line).and_then(module_day).map(|d| (idx, d)))
+        .collect::<Vec<_>>();
+
+    if let Some(&(mut idx, _)) = modules.iter().find(|&&(_, d)| d > day) {
+        // before the next module's attribute
+        if idx > 0 && lines[idx - 1].starts_with("#[") {
+            idx -= 1;
+        }
+        lines.splice(idx..idx, [feature.as_str(), declaration.as_str()]);
+    } else if let Some(&(idx, _)) = modules.last() {
+        lines.splice(idx + 1..idx + 1, [feature.as_str(), declaration.as_str()]);
+    } else {
+        let idx = lines
+            .iter()
+            .position(|line| line.starts_with("include!"))
+            .context("lib.rs does not include the registry")?;
+        lines.splice(idx..idx, [feature.as_str(), declaration.as_str(), ""]);
+    }
+
+    let mut lib = lines.join("\n");
+    lib.push('\n');
+    Ok(lib)
+}
+
+/// Declares the `<module>` feature in the year's `Cargo.toml` and adds it to
+/// `all`, both in day order.
+fn add_feature(manifest: &str, module: &str) -> Result<String> {
+    let mut lines = manifest.lines().map(str::to_owned).collect::<Vec<_>>();
+    let all = lines
+        .iter()
+        .position(|line| line.starts_with("all = ["))
+        .context("no single-line `all` feature")?;
+
+    let list = lines[all]
+        .trim_start_matches("all = [")
+        .trim_end_matches(']');
+    let mut features = list
+        .split(',')
+        .map(|f| f.trim().trim_matches('"'))
+        .filter(|f| !f.is_empty())
+        .collect::<Vec<_>>();
+    if features.contains(&module) {
+        bail!("feature {module} already exists");
+    }
+    features.push(module);
+    features.sort_by_key(|f| module_day(f));
+    let features = features
+        .iter()
+        .map(|f| format!("\"{f}\""))
+        .collect::<Vec<_>>();
+    lines[all] = format!("all = [{}]", features.join(", "));
+
+    let day = module_day(module).unwrap();
+    let feature_day = |line: &str| module_day(line.split_once(" = ")?.0);
+    let idx = match lines
+        .iter()
+        .position(|line| feature_day(line).is_some_and(|d| d > day))
+    {
+        Some(idx) => idx,
+        None => {
+            let last = lines.iter().rposition(|line| feature_day(line).is_some());
+            last.unwrap_or(all) + 1
+        }
+    };
+    lines.insert(idx, format!("{module} = []"));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates `src/dayNN.rs` from a template and declares it in `lib.rs` behind
+/// its own feature, then leaves an empty input file for `aoc fetch` to fill
+/// and an empty example for `example_tests!`. Returns the new files.
+pub fn new_day(workspace: &Path, year: u32, day: u8, template: Template) -> Result<Vec<PathBuf>> {
+    let root = workspace.join(year.to_string());
+    let src = root.join("src");
+    let names = fs::read_dir(&src)?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            Some(path.file_stem()?.to_str()?.to_owned())
+        })
+        .collect::<Vec<_>>();
+    let module = module_name(names.iter().map(String::as_str), day);
+
+    let file = src.join(format!("{module}.rs"));
+    if file.exists() {
+        bail!("{} already exists", file.display());
+    }
+    let lib_path = src.join("lib.rs");
+    let lib = insert_mod(&fs::read_to_string(&lib_path)?, &module)?;
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = add_feature(&fs::read_to_string(&manifest_path)?, &module)
+        .with_context(|| format!("could not update {}", manifest_path.display()))?;
+
+    let source = template
+        .source()
+        .replace("{{name}}", &format!("D{}", &module[1..]))
+        .replace("{{day}}", &day.to_string());
+    fs::write(&file, source)?;
+    fs::write(&lib_path, lib)?;
+    fs::write(&manifest_path, manifest)?;
+    let mut created = vec![file];
+
+    let input = root
+        .join("input")
+        .join(year.to_string())
+        .join(format!("day{day}.txt"));
+    if !input.exists() {
+        fs::create_dir_all(input.parent().unwrap())?;
+        fs::write(&input, "")?;
+        created.push(input);
+    }
+
+    let examples = workspace
+        .join("examples")
+        .join(year.to_string())
+        .join(format!("day{day:02}"));
+    if !examples.exists() {
+        fs::create_dir_all(&examples)?;
+        let (example, answers) = (examples.join("example.txt"), examples.join("answers.toml"));
+        fs::write(&example, "")?;
+        fs::write(&answers, "[example]\n# part1 = \"\"\n# part2 = \"\"\n")?;
+        created.extend([example, answers]);
+    }
+    Ok(created)
+}
+
+fn add_member(manifest: &str, member: &str) -> Result<String> {
+    let mut lines = manifest.lines().map(str::to_owned).collect::<Vec<_>>();
+    let line = lines
+        .iter_mut()
+        .find(|line| line.starts_with("members = ["))
+        .context("workspace has no single-line `members` list")?;
+
+    let list = line.trim_start_matches("members = [").trim_end_matches(']');
+    let mut members = list
+        .split(',')
+        .map(|m| m.trim().trim_matches('"'))
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>();
+    members.push(member);
+    members.sort_unstable();
+
+    let members = members
+        .iter()
+        .map(|m| format!("\"{m}\""))
+        .collect::<Vec<_>>();
+    *line = format!("members = [{}]", members.join(", "));
+    Ok(lines.join("\n") + "\n")
+}
+
+fn insert_after_last(content: &str, prefix: &str, new_line: &str) -> Result<String> {
+    let mut lines = content.lines().collect::<Vec<_>>();
+    let idx = lines
+        .iter()
+        .rposition(|line| line.starts_with(prefix))
+        .with_context(|| format!("no line starting with `{prefix}`"))?;
+    lines.insert(idx + 1, new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn register_year(main: &str, year: u32) -> Result<String> {
+    let mut lines = main.lines().map(str::to_owned).collect::<Vec<_>>();
+    let line = lines
+        .iter_mut()
+        .find(|line| line.starts_with("static YEARS") && line.ends_with("];"))
+        .context("could not find the single-line `YEARS` list in aoc/src/main.rs")?;
+    line.truncate(line.len() - "];".len());
+    line.push_str(&format!(", &aoc{year}::REGISTRY];"));
+    Ok(lines.join("\n") + "\n")
+}
+
+fn edit(path: &Path, f: impl FnOnce(&str) -> Result<String>) -> Result<()> {
+    let content = fs::read_to_string(path)?;
+    let edited = f(&content).with_context(|| format!("could not update {}", path.display()))?;
+    fs::write(path, edited)?;
+    Ok(())
+}
+
+/// Creates a `YYYY/` crate shaped like the existing ones and wires it into
+/// the workspace and the runner.
+pub fn new_year(workspace: &Path, year: u32) -> Result<()> {
+    let root = workspace.join(year.to_string());
+    if root.exists() {
+        bail!("{} already exists", root.display());
+    }
+
+    let fill = |template: &str| template.replace("{{year}}", &year.to_string());
+    fs::create_dir_all(root.join("src"))?;
+    fs::create_dir_all(root.join("input").join(year.to_string()))?;
+    fs::write(
+        root.join("Cargo.toml"),
+        fill(include_str!("../templates/year/Cargo.toml.in")),
+    )?;
+    fs::write(
+        root.join("build.rs"),
+        include_str!("../templates/year/build.rs"),
+    )?;
+    fs::write(
+        root.join("src/lib.rs"),
+        fill(include_str!("../templates/year/lib.rs")),
+    )?;
+
+    edit(&workspace.join("Cargo.toml"), |manifest| {
+        let manifest = add_member(manifest, &year.to_string())?;
+        insert_after_last(
+            &manifest,
+            "aoc20",
+            &format!("aoc{year} = {{ path = \"{year}\" }}"),
+        )
+    })?;
+    edit(&workspace.join("aoc/Cargo.toml"), |manifest| {
+        insert_after_last(manifest, "aoc20", &format!("aoc{year}.workspace = true"))
+    })?;
+    edit(&workspace.join("aoc/src/main.rs"), |main| {
+        register_year(main, year)
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(&["lib", "day1"], 2, "day2")]
+    #[case(&["lib", "day01", "day11"], 12, "day12")]
+    #[case(&["lib", "day01"], 3, "day03")]
+    #[case(&["lib"], 1, "day01")]
+    fn test_module_name(#[case] existing: &[&str], #[case] day: u8, #[case] expected: &str) {
+        assert_eq!(module_name(existing.iter().copied(), day), expected);
+    }
+
+    #[test]
+    fn test_insert_mod_in_order() {
+        let lib = "#[cfg(feature = \"day01\")]\npub mod day01;\n#[cfg(feature = \"day03\")]\npub mod day03;\n\ninclude!(concat!(env!(\"OUT_DIR\"), \"/registry.rs\"));\n";
+        assert_eq!(
+            insert_mod(lib, "day02").unwrap(),
+            "#[cfg(feature = \"day01\")]\npub mod day01;\n#[cfg(feature = \"day02\")]\npub mod day02;\n#[cfg(feature = \"day03\")]\npub mod day03;\n\ninclude!(concat!(env!(\"OUT_DIR\"), \"/registry.rs\"));\n"
+        );
+        assert_eq!(
+            insert_mod(lib, "day12").unwrap(),
+            "#[cfg(feature = \"day01\")]\npub mod day01;\n#[cfg(feature = \"day03\")]\npub mod day03;\n#[cfg(feature = \"day12\")]\npub mod day12;\n\ninclude!(concat!(env!(\"OUT_DIR\"), \"/registry.rs\"));\n"
+        );
+        assert!(insert_mod(lib, "day03").is_err());
+    }
+
+    #[test]
+    fn test_insert_first_mod() {
+        let lib = "include!(concat!(env!(\"OUT_DIR\"), \"/registry.rs\"));\n";
+        assert_eq!(
+            insert_mod(lib, "day01").unwrap(),
+            "#[cfg(feature = \"day01\")]\npub mod day01;\n\ninclude!(concat!(env!(\"OUT_DIR\"), \"/registry.rs\"));\n"
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "[features]\ndefault = [\"all\"]\nall = []\n\n[dependencies]\n",
+        "day01",
+        "[features]\ndefault = [\"all\"]\nall = [\"day01\"]\nday01 = []\n\n[dependencies]\n"
+    )]
+    #[case(
+        "all = [\"day1\", \"day10\"]\nday1 = []\nday10 = [\"dep:petgraph\"]\n",
+        "day2",
+        "all = [\"day1\", \"day2\", \"day10\"]\nday1 = []\nday2 = []\nday10 = [\"dep:petgraph\"]\n"
+    )]
+    #[case(
+        "all = [\"day01\"]\nday01 = []\n\n[dependencies]\n",
+        "day02",
+        "all = [\"day01\", \"day02\"]\nday01 = []\nday02 = []\n\n[dependencies]\n"
+    )]
+    fn test_add_feature(#[case] manifest: &str, #[case] module: &str, #[case] expected: &str) {
+        assert_eq!(add_feature(manifest, module).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_new_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("2015");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(
+            root.join("src/lib.rs"),
+            "#[cfg(feature = \"day1\")]\npub mod day1;\n\ninclude!(concat!(env!(\"OUT_DIR\"), \"/registry.rs\"));\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[features]\ndefault = [\"all\"]\nall = [\"day1\"]\nday1 = []\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/day1.rs"), "").unwrap();
+
+        let created = new_day(dir.path(), 2015, 2, Template::Grid).unwrap();
+        assert_eq!(
+            created,
+            [
+                root.join("src/day2.rs"),
+                root.join("input/2015/day2.txt"),
+                dir.path().join("examples/2015/day02/example.txt"),
+                dir.path().join("examples/2015/day02/answers.toml"),
+            ]
+        );
+        let source = fs::read_to_string(&created[0]).unwrap();
+        assert!(source.contains("impl Day for Day2 {\n    const DAY: u8 = 2;"));
+        assert!(source.contains("Grid::parse(2, input)"));
+        assert!(
+            fs::read_to_string(root.join("src/lib.rs"))
+                .unwrap()
+                .contains("mod day1;\n#[cfg(feature = \"day2\")]\npub mod day2;\n")
+        );
+        assert!(
+            fs::read_to_string(root.join("Cargo.toml"))
+                .unwrap()
+                .contains("all = [\"day1\", \"day2\"]\nday1 = []\nday2 = []\n")
+        );
+        assert!(new_day(dir.path(), 2015, 2, Template::Grid).is_err());
+    }
+
+    #[test]
+    fn test_new_year() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = dir.path();
+        fs::create_dir_all(workspace.join("aoc/src")).unwrap();
+        fs::write(
+            workspace.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"2015\", \"2025\", \"aoc\", \"common\"]\n\n[workspace.dependencies]\naoc-common = { path = \"common\" }\naoc2015 = { path = \"2015\" }\naoc2025 = { path = \"2025\" }\n\nanyhow = \"1.0.100\"\n",
+        )
+        .unwrap();
+        fs::write(
+            workspace.join("aoc/Cargo.toml"),
+            "[dependencies]\nanyhow.workspace = true\naoc2015.workspace = true\naoc2025.workspace = true\nclap.workspace = true\n",
+        )
+        .unwrap();
+        fs::write(
+            workspace.join("aoc/src/main.rs"),
+            "static YEARS: &[&Year] = &[&aoc2015::REGISTRY, &aoc2025::REGISTRY];\n",
+        )
+        .unwrap();
+
+        new_year(workspace, 2026).unwrap();
+
+        assert!(workspace.join("2026/build.rs").exists());
+        assert!(workspace.join("2026/input/2026").is_dir());
+        assert!(
+            fs::read_to_string(workspace.join("2026/Cargo.toml"))
+                .unwrap()
+                .contains("name = \"aoc2026\"")
+        );
+        assert!(
+            fs::read_to_string(workspace.join("2026/src/lib.rs"))
+                .unwrap()
+                .contains("/registry.rs")
+        );
+        assert_eq!(
+            fs::read_to_string(workspace.join("Cargo.toml")).unwrap(),
+            "[workspace]\nmembers = [\"2015\", \"2025\", \"2026\", \"aoc\", \"common\"]\n\n[workspace.dependencies]\naoc-common = { path = \"common\" }\naoc2015 = { path = \"2015\" }\naoc2025 = { path = \"2025\" }\naoc2026 = { path = \"2026\" }\n\nanyhow = \"1.0.100\"\n"
+        );
+        assert!(
+            fs::read_to_string(workspace.join("aoc/Cargo.toml"))
+                .unwrap()
+                .contains("aoc2025.workspace = true\naoc2026.workspace = true\n")
+        );
+        assert_eq!(
+            fs::read_to_string(workspace.join("aoc/src/main.rs")).unwrap(),
+            "static YEARS: &[&Year] = &[&aoc2015::REGISTRY, &aoc2025::REGISTRY, &aoc2026::REGISTRY];\n"
+        );
+        assert!(new_year(workspace, 2026).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+//! A throwaway HTTP server so the client can be tested without reaching
+//! adventofcode.com.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread::{self, JoinHandle},
+};
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub struct Stub {
+    pub url: String,
+    handle: JoinHandle<Vec<Request>>,
+}
+
+impl Stub {
+    /// Answers one connection per `(status, body)` pair, in order.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut request_line = line.split_whitespace();
+                let method = request_line.next().unwrap().to_owned();
+                let path = request_line.next().unwrap().to_owned();
+
+                let mut headers = Vec::new();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    let Some((key, value)) = line.trim_end().split_once(':') else {
+                        break;
+                    };
+                    headers.push((key.to_owned(), value.trim().to_owned()));
+                }
+                let mut request = Request {
+                    method,
+                    path,
+                    headers,
+                    body: String::new(),
+                };
+
+                let length = request
+                    .header("content-length")
+                    .map_or(0, |length| length.parse().unwrap());
+                let mut content = vec![0; length];
+                reader.read_exact(&mut content).unwrap();
+                request.body = String::from_utf8(content).unwrap();
+                requests.push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+
+        Self { url, handle }
+    }
+
+    /// Waits for every response to be served and returns what was received.
+    pub fn requests(self) -> Vec<Request> {
+        self.handle.join().unwrap()
+    }
+}
@@ -0,0 +1,354 @@
+use std::{
+    cmp::Ordering,
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+
+/// How long the site makes you wait after a wrong answer.
+const WRONG_ANSWER_COOLDOWN: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    AlreadySolved,
+    Wait { seconds: u64 },
+}
+
+impl Outcome {
+    fn is_wrong(self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Wrong)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::TooHigh => write!(f, "too high"),
+            Self::TooLow => write!(f, "too low"),
+            Self::Wrong => write!(f, "wrong"),
+            Self::AlreadySolved => write!(f, "already solved"),
+            Self::Wait { seconds } => write!(f, "wait {seconds} seconds"),
+        }
+    }
+}
+
+/// Extracts the outcome from the page returned after posting an answer.
+pub fn parse_response(page: &str) -> Result<Outcome> {
+    let start = page.find("<article>").unwrap_or(0);
+    let end = page[start..]
+        .find("</article>")
+        .map_or(page.len(), |end| start + end);
+    let article = &page[start..end];
+
+    Ok(if article.contains("That's the right answer") {
+        Outcome::Correct
+    } else if let Some(seconds) = parse_wait(article) {
+        Outcome::Wait { seconds }
+    } else if article.contains("your answer is too high") {
+        Outcome::TooHigh
+    } else if article.contains("your answer is too low") {
+        Outcome::TooLow
+    } else if article.contains("That's not the right answer") {
+        Outcome::Wrong
+    } else if article.contains("Did you already complete it") {
+        Outcome::AlreadySolved
+    } else {
+        bail!("unrecognized response:\n{}", strip_tags(article).trim());
+    })
+}
+
+// "You have 1m 32s left to wait." / "You have 45s left to wait."
+fn parse_wait(text: &str) -> Option<u64> {
+    let end = text.find(" left to wait")?;
+    let start = text[..end].rfind("have ")? + "have ".len();
+    text[start..end]
+        .split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.len() - 1);
+            let value = value.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn compare(value: Option<i128>, other: &str) -> Option<Ordering> {
+    Some(value?.cmp(&other.parse().ok()?))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    pub at: u64,
+}
+
+/// Every answer submitted for a year, kept in `submissions.toml` next to the
+/// year's `Cargo.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "submission")]
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    pub fn path(root: &str) -> PathBuf {
+        Path::new(root).join("submissions.toml")
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        if path.exists() {
+            Ok(toml::from_str(&fs::read_to_string(path)?)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    fn for_part(&self, day: u8, part: u8) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |s| s.day == day && s.part == part)
+    }
+
+    /// Refuses answers the site would reject anyway or would not accept yet,
+    /// so no request is wasted on them.
+    pub fn check(&self, day: u8, part: u8, answer: &str, now: u64) -> Result<()> {
+        let value = answer.parse::<i128>().ok();
+        for previous in self.for_part(day, part) {
+            match previous.outcome {
+                Outcome::Correct | Outcome::AlreadySolved => {
+                    bail!("part already solved")
+                }
+                // the site did not judge an answer it rate limited
+                Outcome::Wait { .. } => {}
+                outcome if previous.answer == answer => {
+                    bail!("{answer} was already submitted: {outcome}")
+                }
+                Outcome::TooHigh
+                    if compare(value, &previous.answer).is_some_and(Ordering::is_ge) =>
+                {
+                    bail!("{answer} is too high, {} already was", previous.answer)
+                }
+                Outcome::TooLow
+                    if compare(value, &previous.answer).is_some_and(Ordering::is_le) =>
+                {
+                    bail!("{answer} is too low, {} already was", previous.answer)
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(last) = self.submissions.last() {
+            let ready_at = match last.outcome {
+                Outcome::Wait { seconds } => last.at + seconds,
+                outcome if outcome.is_wrong() => last.at + WRONG_ANSWER_COOLDOWN,
+                _ => 0,
+            };
+            if ready_at > now {
+                bail!("rate limited: wait {} seconds", ready_at - now);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn record(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+}
+
+pub fn submit(
+    client: &Client,
+    history: &mut History,
+    year: u32,
+    (day, part): (u8, u8),
+    answer: &str,
+) -> Result<Outcome> {
+    history.check(day, part, answer, now())?;
+
+    let outcome = parse_response(&client.answer(year, day, part, answer)?)?;
+    history.record(Submission {
+        day,
+        part,
+        answer: answer.to_owned(),
+        outcome,
+        at: now(),
+    });
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::stub::Stub;
+
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main><article><p>{article}</p></article></main></body></html>")
+    }
+
+    #[rstest]
+    #[case(
+        "That's the right answer! You are one gold star closer to decorating the North Pole.",
+        Outcome::Correct
+    )]
+    #[case(
+        "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data; please wait one minute before trying again.",
+        Outcome::TooHigh
+    )]
+    #[case(
+        "That's not the right answer; your answer is too low. Please wait one minute before trying again.",
+        Outcome::TooLow
+    )]
+    #[case(
+        "That's not the right answer. If you're stuck, make sure you're using the full input data; please wait one minute before trying again.",
+        Outcome::Wrong
+    )]
+    #[case(
+        "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 45s left to wait. <a href=\"/2025/day/1\">[Return to Day 1]</a>",
+        Outcome::Wait { seconds: 45 }
+    )]
+    #[case(
+        "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 2s left to wait.",
+        Outcome::Wait { seconds: 242 }
+    )]
+    #[case(
+        "You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2025/day/1\">[Return to Day 1]</a>",
+        Outcome::AlreadySolved
+    )]
+    fn test_parse_response(#[case] article: &str, #[case] expected: Outcome) {
+        assert_eq!(parse_response(&page(article)).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_response_unknown() {
+        let error = parse_response(&page("Something <em>new</em>")).unwrap_err();
+        assert!(error.to_string().ends_with("Something new"));
+    }
+
+    fn history(submissions: &[(&str, Outcome, u64)]) -> History {
+        History {
+            submissions: submissions
+                .iter()
+                .map(|&(answer, outcome, at)| Submission {
+                    day: 1,
+                    part: 1,
+                    answer: answer.to_owned(),
+                    outcome,
+                    at,
+                })
+                .collect(),
+        }
+    }
+
+    #[rstest]
+    #[case(&[], "969", true)]
+    #[case(&[("970", Outcome::TooHigh, 0)], "969", true)]
+    #[case(&[("970", Outcome::TooHigh, 0)], "970", false)]
+    #[case(&[("970", Outcome::TooHigh, 0)], "1000", false)]
+    #[case(&[("900", Outcome::TooLow, 0)], "899", false)]
+    #[case(&[("900", Outcome::TooLow, 0)], "969", true)]
+    #[case(&[("abc", Outcome::Wrong, 0)], "abc", false)]
+    #[case(&[("969", Outcome::Correct, 0)], "969", false)]
+    #[case(&[("1", Outcome::Wrong, 990)], "969", false)]
+    #[case(&[("1", Outcome::Wrong, 900)], "969", true)]
+    #[case(&[("1", Outcome::Wait { seconds: 300 }, 800)], "969", false)]
+    #[case(&[("969", Outcome::Wait { seconds: 300 }, 800)], "969", false)]
+    #[case(&[("969", Outcome::Wait { seconds: 60 }, 800)], "969", true)]
+    fn test_history_check(
+        #[case] submissions: &[(&str, Outcome, u64)],
+        #[case] answer: &str,
+        #[case] allowed: bool,
+    ) {
+        assert_eq!(
+            history(submissions).check(1, 1, answer, 1000).is_ok(),
+            allowed
+        );
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let history = history(&[
+            ("969", Outcome::TooHigh, 10),
+            ("1", Outcome::Wait { seconds: 30 }, 20),
+        ]);
+        let parsed: History = toml::from_str(&toml::to_string(&history).unwrap()).unwrap();
+        assert_eq!(parsed.submissions, history.submissions);
+    }
+
+    #[test]
+    fn test_submit_posts_answer() {
+        let stub = Stub::serve(vec![(
+            200,
+            "<article><p>That's the right answer!</p></article>",
+        )]);
+        let client = Client::new(&stub.url, "cafe");
+        let mut history = History::default();
+
+        let outcome = submit(&client, &mut history, 2025, (1, 2), "5887").unwrap();
+        assert_eq!(outcome, Outcome::Correct);
+        assert_eq!(history.submissions.len(), 1);
+        assert_eq!(history.submissions[0].answer, "5887");
+
+        let requests = stub.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2025/day/1/answer");
+        assert_eq!(requests[0].header("cookie"), Some("session=cafe"));
+        assert_eq!(requests[0].body, "level=2&answer=5887");
+    }
+
+    #[test]
+    fn test_submit_skips_known_answer() {
+        let stub = Stub::serve(vec![]);
+        let client = Client::new(&stub.url, "cafe");
+        let mut history = history(&[("969", Outcome::TooHigh, 0)]);
+
+        assert!(submit(&client, &mut history, 2025, (1, 1), "969").is_err());
+        assert_eq!(history.submissions.len(), 1);
+        assert!(stub.requests().is_empty());
+    }
+}
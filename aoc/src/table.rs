@@ -0,0 +1,70 @@
+use std::fmt::Write;
+
+fn widths(headers: &[&str], rows: &[Vec<String>]) -> Vec<usize> {
+    let mut widths = headers.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    widths
+}
+
+/// Left-aligns every column to its widest cell.
+pub fn render(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let widths = widths(headers, rows);
+
+    let mut out = String::new();
+    let mut line = |cells: &mut dyn Iterator<Item = &str>| {
+        let row = cells
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", row.join("  ").trim_end()).unwrap();
+    };
+    line(&mut headers.iter().copied());
+    for row in rows {
+        line(&mut row.iter().map(String::as_str));
+    }
+    out
+}
+
+/// The same table in GitHub-flavored Markdown, padded to stay readable as text.
+pub fn markdown(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let widths = widths(headers, rows);
+    let mut out = String::new();
+    let mut line = |cells: &mut dyn Iterator<Item = &str>| {
+        let row = cells
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>();
+        writeln!(out, "| {} |", row.join(" | ")).unwrap();
+    };
+    line(&mut headers.iter().copied());
+    let rule = widths
+        .iter()
+        .map(|&width| "-".repeat(width))
+        .collect::<Vec<_>>();
+    line(&mut rule.iter().map(String::as_str));
+    for row in rows {
+        line(&mut row.iter().map(String::as_str));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let rows = vec![
+            vec!["bruteforce".to_owned(), "5887".to_owned()],
+            vec!["smart".to_owned(), "5887".to_owned()],
+        ];
+        assert_eq!(
+            render(&["variant", "answer"], &rows),
+            "variant     answer\nbruteforce  5887\nsmart       5887\n"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use anyhow::{Result, bail};
+use aoc_common::{Solver, Year};
+
+use crate::{answers::Answers, run};
+
+/// Runs every selected solver and compares it with the recorded answer.
+pub fn verify(solvers: &[(&Year, &Solver)], options: &run::Options) -> Result<()> {
+    let mut answers = HashMap::new();
+    let mut inputs = HashMap::new();
+    let mut failures = 0;
+
+    for &(year, solver) in solvers {
+        let answers = match answers.entry(year.year) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(Answers::load(&Answers::path(year.root))?),
+        };
+        let Some(expected) = answers.get(solver.day, solver.part) else {
+            println!("{} {solver}: no recorded answer", year.year);
+            continue;
+        };
+
+        // a missing input skips its day, like a missing answer
+        let input = match inputs.entry((year.year, solver.day)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(run::read_input(year, solver.day)),
+        };
+        let input = match input {
+            Ok(input) => input,
+            Err(e) => {
+                println!("{} {solver}: SKIPPED, {e}", year.year);
+                continue;
+            }
+        };
+        match run::solve(year, solver, input, options) {
+            Ok(solution) if solution.answer == expected => {
+                println!("{} {solver}: ok", year.year);
+            }
+            Ok(solution) => {
+                failures += 1;
+                println!(
+                    "{} {solver}: MISMATCH, expected {expected}, got {}",
+                    year.year, solution.answer
+                );
+            }
+            Err(failure) => {
+                failures += 1;
+                println!("{} {solver}: {failure}", year.year);
+            }
+        }
+    }
+
+    if failures > 0 {
+        bail!("{failures} solver(s) failed verification");
+    }
+    Ok(())
+}
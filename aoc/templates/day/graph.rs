@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+
+use aoc_common::{Day, parse::ParseError};
+
+type ParsedInput = HashMap<String, Vec<String>>;
+
+pub struct {{name}};
+
+impl Day for {{name}} {
+    const DAY: u8 = {{day}};
+    type Parsed = ParsedInput;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<ParsedInput, ParseError> {
+        input
+            .lines()
+            .map(|line| {
+                let (node, neighbours) = line
+                    .split_once(": ")
+                    .ok_or_else(|| ParseError::at({{day}}, input, &line[line.len()..], "`: `"))?;
+                Ok((
+                    node.to_owned(),
+                    neighbours.split_whitespace().map(str::to_owned).collect(),
+                ))
+            })
+            .collect()
+    }
+
+    fn part1(_input: &ParsedInput) -> u64 {
+        todo!()
+    }
+
+    fn part2(_input: &ParsedInput) -> u64 {
+        todo!()
+    }
+}
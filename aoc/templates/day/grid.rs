@@ -0,0 +1,23 @@
+use aoc_common::{Day, grid::Grid, parse::ParseError};
+
+type ParsedInput = Grid<char>;
+
+pub struct {{name}};
+
+impl Day for {{name}} {
+    const DAY: u8 = {{day}};
+    type Parsed = ParsedInput;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<ParsedInput, ParseError> {
+        Grid::parse({{day}}, input)
+    }
+
+    fn part1(_input: &ParsedInput) -> u64 {
+        todo!()
+    }
+
+    fn part2(_input: &ParsedInput) -> u64 {
+        todo!()
+    }
+}
@@ -0,0 +1,21 @@
+use aoc_common::{Day, parse::ParseError};
+
+pub struct {{name}};
+
+impl Day for {{name}} {
+    const DAY: u8 = {{day}};
+    type Parsed = Vec<String>;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        Ok(input.lines().map(str::to_owned).collect())
+    }
+
+    fn part1(_input: &Vec<String>) -> u64 {
+        todo!()
+    }
+
+    fn part2(_input: &Vec<String>) -> u64 {
+        todo!()
+    }
+}
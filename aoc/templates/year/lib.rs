@@ -0,0 +1,3 @@
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+aoc_common::example_tests!();
@@ -0,0 +1,59 @@
+//! Named assumptions about the puzzle input that a day's solvers rely on.
+//!
+//! A day opts in by overriding [`Day::assumptions`](crate::Day::assumptions).
+//! The runner checks them before solving, so an input breaking one is
+//! explained instead of producing a wrong answer or an index out of bounds.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assumption {
+    pub name: &'static str,
+    /// Where the input breaks the assumption, if it does.
+    pub violation: Option<String>,
+}
+
+impl Assumption {
+    /// Holds unless `find` turns up a violation, such as the first offending line.
+    pub fn unless(name: &'static str, find: Option<String>) -> Self {
+        Self {
+            name,
+            violation: find,
+        }
+    }
+
+    pub fn holds(&self) -> bool {
+        self.violation.is_none()
+    }
+}
+
+impl fmt::Display for Assumption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.violation {
+            None => write!(f, "ok: {}", self.name),
+            Some(violation) if violation.is_empty() => write!(f, "FAILED: {}", self.name),
+            Some(violation) => write!(f, "FAILED: {} ({violation})", self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Assumption::unless("the graph has no cycles", None).to_string(),
+            "ok: the graph has no cycles"
+        );
+        assert_eq!(
+            Assumption::unless("the second line is empty", Some("line 2 is `..^..`".into()))
+                .to_string(),
+            "FAILED: the second line is empty (line 2 is `..^..`)"
+        );
+        let cyclic = Assumption::unless("the graph has no cycles", Some(String::new()));
+        assert!(!cyclic.holds());
+        assert_eq!(cyclic.to_string(), "FAILED: the graph has no cycles");
+    }
+}
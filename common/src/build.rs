@@ -0,0 +1,424 @@
+//! Build-script side of the solver registry.
+//!
+//! Year crates call [`generate_registry`] from their `build.rs`. It scans
+//! `src/` for `impl Day for ...` blocks and writes `$OUT_DIR/registry.rs`,
+//! which `lib.rs` includes to expose a `REGISTRY` static.
+//!
+//! Modules declared behind `#[cfg(feature = "...")]` in `lib.rs` are only
+//! registered when their feature is enabled.
+//!
+//! It also reads `examples/<year>/dayNN/`, where each `<name>.txt` example is
+//! listed in `answers.toml` with its expected answers, and writes
+//! `$OUT_DIR/examples.rs` for [`example_tests!`](crate::example_tests).
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A type implementing `Day`, the module it lives in and its named variants.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Entry {
+    day: u8,
+    module: String,
+    ty: String,
+    /// The `PART1_NAME` and `PART2_NAME` of the plain parts.
+    names: [Option<String>; 2],
+    /// `(part, name)` of each `Variant` the module declares.
+    variants: Vec<(u8, String)>,
+}
+
+impl Entry {
+    fn parse(module: &str, content: &str) -> Option<Self> {
+        let mut lines = content.lines().map(str::trim);
+        let ty = lines.find_map(|line| {
+            let rest = line.strip_prefix("impl ")?;
+            let rest = rest.strip_prefix("aoc_common::").unwrap_or(rest);
+            rest.strip_prefix("Day for ")?.strip_suffix(" {")
+        })?;
+        let day = lines.find_map(|line| {
+            let day = line.strip_prefix("const DAY: u8 = ")?.strip_suffix(';')?;
+            day.parse().ok()
+        })?;
+        Some(Self {
+            day,
+            module: module.to_owned(),
+            ty: ty.to_owned(),
+            names: [part_name(content, 1), part_name(content, 2)],
+            variants: variants(content),
+        })
+    }
+
+    /// The parts and variants that solve `part`, the plain part first.
+    fn solvers(&self, part: u8) -> Vec<Option<&str>> {
+        let plain = (1..=2)
+            .contains(&part)
+            .then(|| self.names[part as usize - 1].as_deref());
+        let variants = self.variants.iter().filter(|(p, _)| *p == part);
+        plain
+            .into_iter()
+            .chain(variants.map(|(_, name)| Some(name.as_str())))
+            .collect()
+    }
+}
+
+/// The name given by `const PARTn_NAME: Option<&'static str> = Some("...");`.
+fn part_name(content: &str, part: u8) -> Option<String> {
+    let prefix = format!("const PART{part}_NAME: Option<&'static str> = Some(\"");
+    content.lines().find_map(|line| {
+        let name = line.trim().strip_prefix(&prefix)?.strip_suffix("\");")?;
+        Some(name.to_owned())
+    })
+}
+
+/// `(part, name)` of each `Variant { part: N, name: "...", .. }` in `content`.
+fn variants(content: &str) -> Vec<(u8, String)> {
+    content
+        .split("Variant {")
+        .skip(1)
+        .filter_map(|block| {
+            let part = block.split_once("part: ")?.1;
+            let part = part[..part.find(|c: char| !c.is_ascii_digit())?]
+                .parse()
+                .ok()?;
+            let name = block.split_once("name: \"")?.1;
+            let name = &name[..name.find('"')?];
+            Some((part, name.to_owned()))
+        })
+        .collect()
+}
+
+/// Year crates are named `aocYYYY`.
+fn parse_year(package: &str) -> Option<u32> {
+    package.strip_prefix("aoc")?.parse().ok()
+}
+
+/// Modules declared in `lib.rs` behind `#[cfg(feature = "...")]`, with their feature.
+fn gated_modules(lib: &str) -> BTreeMap<String, String> {
+    let lines = lib.lines().map(str::trim).collect::<Vec<_>>();
+    lines
+        .windows(2)
+        .filter_map(|pair| {
+            let feature = pair[0]
+                .strip_prefix("#[cfg(feature = \"")?
+                .strip_suffix("\")]")?;
+            let declaration = pair[1].strip_prefix("pub ").unwrap_or(pair[1]);
+            let module = declaration.strip_prefix("mod ")?.strip_suffix(';')?;
+            Some((module.to_owned(), feature.to_owned()))
+        })
+        .collect()
+}
+
+fn feature_enabled(feature: &str) -> bool {
+    let var = format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"));
+    env::var_os(var).is_some()
+}
+
+#[derive(Debug, Default)]
+struct Scan {
+    entries: Vec<Entry>,
+    /// Days whose module is left out by a disabled feature.
+    disabled: BTreeSet<u8>,
+}
+
+/// The days in `src/`, skipping modules whose feature is disabled.
+fn scan(src: &Path, gated: &BTreeMap<String, String>) -> Scan {
+    let mut scan = Scan::default();
+    for file in fs::read_dir(src).expect("Could not read src directory") {
+        let path = file.unwrap().path();
+        if path.extension().is_some_and(|ext| ext == "rs") {
+            let module = path.file_stem().unwrap().to_str().unwrap();
+            let content = fs::read_to_string(&path).unwrap();
+            let Some(entry) = Entry::parse(module, &content) else {
+                continue;
+            };
+            if gated
+                .get(module)
+                .is_some_and(|feature| !feature_enabled(feature))
+            {
+                scan.disabled.insert(entry.day);
+            } else {
+                scan.entries.push(entry);
+            }
+        }
+    }
+    scan.entries.sort();
+    scan
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Example {
+    day: u8,
+    name: String,
+    path: PathBuf,
+    answers: BTreeMap<u8, String>,
+}
+
+impl Example {
+    fn test_name(&self, part: u8, variant: Option<&str>) -> String {
+        let name = self
+            .name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        let mut test = format!("day{:02}_{name}_part{part}", self.day);
+        if let Some(variant) = variant {
+            test += &format!("_{}", variant.to_lowercase());
+        }
+        test
+    }
+}
+
+// `[name] partN = "answer"`, integers are accepted as well
+fn parse_answers(day: u8, dir: &Path, answers: &str) -> Result<Vec<Example>, String> {
+    let table: BTreeMap<String, BTreeMap<String, toml::Value>> =
+        toml::from_str(answers).map_err(|e| e.to_string())?;
+
+    table
+        .into_iter()
+        .map(|(name, parts)| {
+            let answers = parts
+                .into_iter()
+                .map(|(key, value)| {
+                    let part = key
+                        .strip_prefix("part")
+                        .and_then(|part| part.parse().ok())
+                        .ok_or_else(|| format!("[{name}]: `{key}` is not `partN`"))?;
+                    let answer = match value {
+                        toml::Value::String(answer) => answer,
+                        toml::Value::Integer(answer) => answer.to_string(),
+                        value => return Err(format!("[{name}]: unexpected answer {value}")),
+                    };
+                    Ok((part, answer))
+                })
+                .collect::<Result<_, String>>()?;
+            let path = dir.join(format!("{name}.txt"));
+            Ok(Example {
+                day,
+                name,
+                path,
+                answers,
+            })
+        })
+        .collect()
+}
+
+fn scan_examples(dir: &Path) -> Vec<Example> {
+    let Ok(days) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut examples = Vec::new();
+    for day_dir in days {
+        let day_dir = day_dir.unwrap().path();
+        let Some(day) = day_dir
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_prefix("day")?.parse().ok())
+        else {
+            continue;
+        };
+        let answers = day_dir.join("answers.toml");
+        let Ok(content) = fs::read_to_string(&answers) else {
+            continue;
+        };
+        let day_examples = parse_answers(day, &day_dir, &content)
+            .unwrap_or_else(|e| panic!("{}: {e}", answers.display()));
+        examples.extend(day_examples);
+    }
+    examples.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    examples
+}
+
+fn tested_parts(examples: &[Example]) -> Vec<(u8, u8)> {
+    let parts = examples
+        .iter()
+        .flat_map(|example| example.answers.keys().map(|&part| (example.day, part)));
+    parts.collect::<BTreeSet<_>>().into_iter().collect()
+}
+
+fn generate_examples(examples: &[Example], entries: &[Entry]) -> String {
+    let mut out = String::new();
+    for example in examples {
+        assert!(
+            example.path.exists(),
+            "{} is listed in answers.toml but does not exist",
+            example.path.display()
+        );
+        let entry = entries.iter().find(|entry| entry.day == example.day);
+        for (&part, answer) in &example.answers {
+            let solvers = entry.map_or_else(Vec::new, |entry| entry.solvers(part));
+            assert!(
+                !solvers.is_empty(),
+                "example {} expects an answer for day {} part {part}, which has no solver",
+                example.path.display(),
+                example.day
+            );
+            for variant in solvers {
+                writeln!(out, "#[test]").unwrap();
+                writeln!(out, "fn {}() {{", example.test_name(part, variant)).unwrap();
+                writeln!(
+                    out,
+                    "    crate::REGISTRY.check_example({}, {part}, {variant:?}, include_str!({:?}), {answer:?});",
+                    example.day, example.path
+                )
+                .unwrap();
+                writeln!(out, "}}").unwrap();
+            }
+        }
+    }
+    out
+}
+
+pub fn generate_registry() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let src = Path::new(&manifest_dir).join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let package = env::var("CARGO_PKG_NAME").unwrap();
+    let year = parse_year(&package).expect("year crates are named `aocYYYY`");
+    let lib = fs::read_to_string(src.join("lib.rs")).unwrap();
+    let Scan { entries, disabled } = scan(&src, &gated_modules(&lib));
+
+    let mut out = String::new();
+    writeln!(out, "static DAYS: &[&dyn ::aoc_common::AnyDay] = &[").unwrap();
+    for Entry { module, ty, .. } in &entries {
+        writeln!(out, "    &crate::{module}::{ty},").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "pub static REGISTRY: ::aoc_common::Year = ::aoc_common::Year {{"
+    )
+    .unwrap();
+    writeln!(out, "    year: {year},").unwrap();
+    writeln!(out, "    root: env!(\"CARGO_MANIFEST_DIR\"),").unwrap();
+    writeln!(out, "    days: DAYS,").unwrap();
+    writeln!(
+        out,
+        "    solvers: ::std::sync::LazyLock::new(|| ::aoc_common::Solver::all(DAYS)),"
+    )
+    .unwrap();
+
+    let examples = Path::new(&manifest_dir).join("../examples");
+    println!("cargo:rerun-if-changed={}", examples.display());
+    let mut examples = scan_examples(&examples.join(year.to_string()));
+    examples.retain(|example| !disabled.contains(&example.day));
+    writeln!(out, "    tested: &{:?},", tested_parts(&examples)).unwrap();
+    writeln!(out, "}};").unwrap();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), out).unwrap();
+    fs::write(
+        Path::new(&out_dir).join("examples.rs"),
+        generate_examples(&examples, &entries),
+    )
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("pub struct Day01;\n\nimpl Day for Day01 {\n    const DAY: u8 = 1;", Some((1, "Day01")))]
+    #[case("impl aoc_common::Day for Day11 {\n    const DAY: u8 = 11;", Some((11, "Day11")))]
+    #[case("impl Day for Day04 {\n    type Parsed = Grid;", None)]
+    #[case("const DAY: u8 = 7;\nimpl Display for Day07 {", None)]
+    fn test_entry_parse(#[case] content: &str, #[case] expected: Option<(u8, &str)>) {
+        let expected = expected.map(|(day, ty)| Entry {
+            day,
+            module: "dayNN".to_owned(),
+            ty: ty.to_owned(),
+            names: [None, None],
+            variants: Vec::new(),
+        });
+        assert_eq!(Entry::parse("dayNN", content), expected);
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let dir = Path::new("examples/2025/day11");
+        let examples = parse_answers(
+            11,
+            dir,
+            "[example]\npart1 = \"5\"\n\n[example2]\npart2 = 2\n",
+        )
+        .unwrap();
+        assert_eq!(
+            examples,
+            [
+                Example {
+                    day: 11,
+                    name: "example".to_owned(),
+                    path: dir.join("example.txt"),
+                    answers: BTreeMap::from([(1, "5".to_owned())]),
+                },
+                Example {
+                    day: 11,
+                    name: "example2".to_owned(),
+                    path: dir.join("example2.txt"),
+                    answers: BTreeMap::from([(2, "2".to_owned())]),
+                },
+            ]
+        );
+        assert!(parse_answers(11, dir, "[example]\nanswer = \"5\"\n").is_err());
+    }
+
+    #[test]
+    fn test_entry_variants() {
+        let content = "impl Day for Day01 {\n    const DAY: u8 = 1;\n    const PART1_NAME: Option<&'static str> = Some(\"decl\");\n\n    fn variants() -> &'static [Variant<Self>] {\n        &[\n            Variant {\n                part: 1,\n                name: \"iterator\",\n                solve: |input| solve_part1_it(input),\n            },\n            Variant { part: 2, name: \"bruteforce\", solve: solve_part2_bf },\n        ]\n    }";
+        let entry = Entry::parse("day01", content).unwrap();
+        assert_eq!(
+            entry.variants,
+            [(1, "iterator".to_owned()), (2, "bruteforce".to_owned())]
+        );
+        assert_eq!(entry.names, [Some("decl".to_owned()), None]);
+        assert_eq!(entry.solvers(1), [Some("decl"), Some("iterator")]);
+        assert_eq!(entry.solvers(2), [None, Some("bruteforce")]);
+        assert!(entry.solvers(3).is_empty());
+    }
+
+    #[rstest]
+    #[case("example", 2, None, "day04_example_part2")]
+    #[case("example-2", 1, None, "day04_example_2_part1")]
+    #[case("example", 1, Some("Simple"), "day04_example_part1_simple")]
+    fn test_example_test_name(
+        #[case] name: &str,
+        #[case] part: u8,
+        #[case] variant: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let example = Example {
+            day: 4,
+            name: name.to_owned(),
+            path: PathBuf::new(),
+            answers: BTreeMap::new(),
+        };
+        assert_eq!(example.test_name(part, variant), expected);
+    }
+
+    #[test]
+    fn test_gated_modules() {
+        let lib = "mod util;\n#[cfg(feature = \"day01\")]\nmod day01;\n\n#[cfg(test)]\nmod tests;\n#[cfg(feature = \"day08\")]\npub mod day08;\n";
+        assert_eq!(
+            gated_modules(lib),
+            BTreeMap::from([
+                ("day01".to_owned(), "day01".to_owned()),
+                ("day08".to_owned(), "day08".to_owned()),
+            ])
+        );
+    }
+
+    #[rstest]
+    #[case("aoc2015", Some(2015))]
+    #[case("aoc2025", Some(2025))]
+    #[case("aoc-common", None)]
+    fn test_parse_year(#[case] package: &str, #[case] expected: Option<u32>) {
+        assert_eq!(parse_year(package), expected);
+    }
+}
@@ -0,0 +1,79 @@
+//! Cooperative cancellation for solvers that can run for a long time.
+//!
+//! Solver signatures are fixed by the `Day` trait, so the runner installs a
+//! [`Token`] on the thread a solver runs on and raises it when a `--timeout`
+//! expires. Slow solvers call [`checkpoint`] in their hot loops to give up.
+
+use std::{
+    cell::RefCell,
+    panic,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering::Relaxed},
+    },
+};
+
+/// Cancels one run. It stays raised once cancelled, so a solver that is given
+/// up on keeps unwinding at its next checkpoint, however late.
+#[derive(Debug, Clone, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Relaxed)
+    }
+
+    /// Makes this the token [`checkpoint`] checks on the current thread.
+    pub fn install(&self) {
+        CURRENT.set(Some(self.clone()));
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+/// Unwinding payload used by [`checkpoint`].
+pub struct Cancelled;
+
+/// Whether the token installed on this thread is raised.
+pub fn is_cancelled() -> bool {
+    CURRENT.with_borrow(|token| token.as_ref().is_some_and(Token::is_cancelled))
+}
+
+/// Unwinds out of the solver once the runner has given up on it. Cheap
+/// enough to call on every iteration.
+pub fn checkpoint() {
+    if is_cancelled() {
+        // unlike `panic!`, this does not run the panic hook
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_unwinds_once_cancelled() {
+        checkpoint();
+        let token = Token::new();
+        token.install();
+        checkpoint();
+        token.cancel();
+        let payload = panic::catch_unwind(checkpoint).unwrap_err();
+        assert!(payload.is::<Cancelled>());
+        // other threads run with their own token, or none
+        assert!(!thread::spawn(is_cancelled).join().unwrap());
+    }
+}
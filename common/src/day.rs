@@ -0,0 +1,177 @@
+//! The solver API. Each day is a unit struct implementing [`Day`], and its
+//! year's generated `REGISTRY` lists it as an [`AnyDay`].
+
+use std::fmt::Display;
+
+use crate::{assume::Assumption, parse::ParseError};
+
+pub trait Day: 'static {
+    const DAY: u8;
+    type Parsed: 'static;
+    type Answer: Display + 'static;
+
+    /// Names for the plain parts, for days that solve a part several ways.
+    const PART1_NAME: Option<&'static str> = None;
+    const PART2_NAME: Option<&'static str> = None;
+
+    /// Gets the input without its trailing newlines.
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(input: &Self::Parsed) -> Self::Answer;
+    fn part2(input: &Self::Parsed) -> Self::Answer;
+
+    /// Other named solutions to either part.
+    fn variants() -> &'static [Variant<Self>] {
+        &[]
+    }
+
+    /// What the solvers take for granted about the input, checked by the
+    /// runner before solving, see [`assume`](crate::assume).
+    fn assumptions(_input: &Self::Parsed) -> Vec<Assumption> {
+        Vec::new()
+    }
+}
+
+pub struct Variant<D: Day + ?Sized> {
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: fn(&D::Parsed) -> D::Answer,
+}
+
+/// A solver with its input parsed, ready to run.
+pub trait Prepared {
+    fn run(&self) -> Box<dyn Display>;
+}
+
+struct Ready<D: Day> {
+    parsed: D::Parsed,
+    solve: fn(&D::Parsed) -> D::Answer,
+}
+
+impl<D: Day> Prepared for Ready<D> {
+    fn run(&self) -> Box<dyn Display> {
+        Box::new((self.solve)(&self.parsed))
+    }
+}
+
+/// A [`Day`] with its types erased, so a year can list all of its days.
+pub trait AnyDay: Sync {
+    fn day(&self) -> u8;
+
+    /// `(part, variant)` of each solver, the plain parts first.
+    fn solvers(&self) -> Vec<(u8, Option<&'static str>)>;
+
+    /// Parses `input` for one of the [`solvers`](Self::solvers).
+    fn prepare(
+        &self,
+        input: &str,
+        part: u8,
+        variant: Option<&str>,
+    ) -> Result<Box<dyn Prepared>, ParseError>;
+
+    /// Checks the day's assumptions, or nothing if `input` does not parse:
+    /// that is for the solvers to report.
+    fn assumptions(&self, input: &str) -> Vec<Assumption>;
+}
+
+impl<D: Day + Sync> AnyDay for D {
+    fn day(&self) -> u8 {
+        D::DAY
+    }
+
+    fn solvers(&self) -> Vec<(u8, Option<&'static str>)> {
+        let variants = D::variants().iter().map(|v| (v.part, Some(v.name)));
+        [(1, D::PART1_NAME), (2, D::PART2_NAME)]
+            .into_iter()
+            .chain(variants)
+            .collect()
+    }
+
+    fn prepare(
+        &self,
+        input: &str,
+        part: u8,
+        variant: Option<&str>,
+    ) -> Result<Box<dyn Prepared>, ParseError> {
+        let solve = match (part, variant) {
+            (1, variant) if variant == D::PART1_NAME => D::part1,
+            (2, variant) if variant == D::PART2_NAME => D::part2,
+            (part, Some(name)) => {
+                let variant = D::variants()
+                    .iter()
+                    .find(|v| (v.part, v.name) == (part, name));
+                variant.expect("solver is registered").solve
+            }
+            (part, None) => panic!("day {} has no part {part}", D::DAY),
+        };
+        Ok(Box::new(Ready::<D> {
+            parsed: D::parse(input.trim_end_matches('\n'))?,
+            solve,
+        }))
+    }
+
+    fn assumptions(&self, input: &str) -> Vec<Assumption> {
+        D::parse(input.trim_end_matches('\n'))
+            .map_or_else(|_| Vec::new(), |parsed| D::assumptions(&parsed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sums;
+
+    impl Day for Sums {
+        const DAY: u8 = 1;
+        type Parsed = Vec<u32>;
+        type Answer = u32;
+
+        const PART2_NAME: Option<&'static str> = Some("max");
+
+        fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+            input
+                .lines()
+                .map(|line| crate::parse::number(1, input, line))
+                .collect()
+        }
+
+        fn part1(input: &Self::Parsed) -> u32 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Self::Parsed) -> u32 {
+            input.iter().max().copied().unwrap_or_default()
+        }
+
+        fn variants() -> &'static [Variant<Self>] {
+            &[Variant {
+                part: 1,
+                name: "loop",
+                solve: |input| {
+                    let mut sum = 0;
+                    for n in input {
+                        sum += n;
+                    }
+                    sum
+                },
+            }]
+        }
+    }
+
+    #[test]
+    fn test_any_day() {
+        let day: &dyn AnyDay = &Sums;
+        assert_eq!(
+            day.solvers(),
+            [(1, None), (2, Some("max")), (1, Some("loop"))]
+        );
+        let answer = |part, variant| {
+            let prepared = day.prepare("3\n4", part, variant).unwrap();
+            prepared.run().to_string()
+        };
+        assert_eq!(answer(1, None), "7");
+        assert_eq!(answer(2, Some("max")), "4");
+        assert_eq!(answer(1, Some("loop")), "7");
+        assert_eq!(day.prepare("3\nx", 1, None).err().unwrap().line, 2);
+    }
+}
@@ -0,0 +1,125 @@
+//! Union-find over the elements `0..n`, for puzzles that merge things into
+//! clusters.
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    /// Only up to date for roots.
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSet {
+    /// `n` elements, each in a set of its own.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            count: n,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// The representative of the set holding `x`, compressing the path to it.
+    pub fn find(&mut self, x: usize) -> usize {
+        let root = self.root(x);
+        let mut x = x;
+        while self.parent[x] != root {
+            x = std::mem::replace(&mut self.parent[x], root);
+        }
+        root
+    }
+
+    fn root(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the sets of `a` and `b`, the smaller one under the larger.
+    /// Returns whether they were apart.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The size of the set holding `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// How many sets there are.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Every set, each in increasing order and ordered by its smallest element.
+    pub fn components(&self) -> impl Iterator<Item = Vec<usize>> + use<> {
+        let mut components = BTreeMap::<usize, Vec<usize>>::new();
+        for x in 0..self.len() {
+            components.entry(self.root(x)).or_default().push(x);
+        }
+        let mut components = components.into_values().collect::<Vec<_>>();
+        components.sort_unstable_by_key(|component| component[0]);
+        components.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union() {
+        let mut set = DisjointSet::new(6);
+        assert!(set.union(0, 1));
+        assert!(set.union(4, 1));
+        assert!(set.union(2, 3));
+        assert!(!set.union(0, 4));
+        assert!(set.same(0, 4));
+        assert!(!set.same(0, 2));
+        assert_eq!(set.size_of(4), 3);
+        assert_eq!(set.size_of(5), 1);
+        assert_eq!(set.count(), 3);
+        assert_eq!(
+            set.components().collect::<Vec<_>>(),
+            [vec![0, 1, 4], vec![2, 3], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_find_compresses() {
+        let mut set = DisjointSet::new(4);
+        // equal sizes keep the first root, so this builds the chain 3 -> 2 -> 0
+        set.union(2, 3);
+        set.union(0, 1);
+        set.union(0, 2);
+        assert_eq!(set.parent[3], 2);
+        assert_eq!(set.find(3), 0);
+        assert_eq!(set.parent[3], 0);
+    }
+}
@@ -0,0 +1,272 @@
+//! A rectangular grid of cells, as most map-like puzzle inputs are.
+//!
+//! Positions are `(x, y)` with `x` the column and `y` the row, from the top
+//! left corner.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::ParseError;
+
+pub type Pos = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row by row.
+    cells: Vec<T>,
+}
+
+impl Grid<char> {
+    /// One row per line, which must all be as long as the first.
+    pub fn parse(day: u8, input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(day, input, "a character", Some)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Like [`Grid::parse`], with `cell` turning each character into a
+    /// cell, or `None` for a character that is not `expected` there.
+    pub fn parse_with(
+        day: u8,
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for line in input.lines() {
+            let mut len = 0;
+            for (idx, c) in line.char_indices() {
+                if len == width {
+                    return Err(ParseError::at(day, input, &line[idx..], "end of line"));
+                }
+                cells.push(
+                    cell(c).ok_or_else(|| ParseError::at(day, input, &line[idx..], expected))?,
+                );
+                len += 1;
+            }
+            if len < width {
+                return Err(ParseError::at(day, input, &line[line.len()..], expected));
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Builds a grid with `cell(x, y)` at each position.
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut cell)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.1 * self.width + pos.0])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.1 * self.width + pos.0])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// The positions sharing a side with `pos` and inside the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.offsets(pos, &[(0, -1), (-1, 0), (1, 0), (0, 1)])
+    }
+
+    /// The positions sharing a side or a corner with `pos` and inside the grid.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        const OFFSETS: [(isize, isize); 8] = [
+            (-1, -1),
+            (0, -1),
+            (1, -1),
+            (-1, 0),
+            (1, 0),
+            (-1, 1),
+            (0, 1),
+            (1, 1),
+        ];
+        self.offsets(pos, &OFFSETS)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        (x, y): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            self.contains(pos).then_some(pos)
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` would yield nothing for a grid of empty rows
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is out of a grid {} wide",
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The positions of every cell equal to `value`, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Pos> + 'a
+    where
+        T: PartialEq,
+    {
+        self.positions().filter(move |&pos| self[pos] == *value)
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is out of a {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is out of a {width}x{height} grid"))
+    }
+}
+
+/// One line per row, as the grid was parsed.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse(4, "ab.\n.@c").unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((1, 1)), Some(&'@'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), "ab.\n.@c");
+    }
+
+    #[rstest]
+    #[case("ab\nabc", (2, 3, "end of line"))]
+    #[case("abc\nab\nabc", (2, 3, "`.` or `@`"))]
+    #[case("..\n.#", (2, 2, "`.` or `@`"))]
+    fn test_parse_with_errors(
+        #[case] input: &str,
+        #[case] (line, column, expected): (usize, usize, &str),
+    ) {
+        let error = Grid::parse_with(4, input, "`.` or `@`", |c| "abc.@".contains(c).then_some(c))
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (line, column));
+        assert_eq!(error.expected, expected);
+    }
+
+    #[rstest]
+    #[case((0, 0), vec![(1, 0), (0, 1)], vec![(1, 0), (0, 1), (1, 1)])]
+    #[case((1, 1), vec![(1, 0), (0, 1), (2, 1)], vec![(0, 0), (1, 0), (2, 0), (0, 1), (2, 1)])]
+    fn test_neighbors(#[case] pos: Pos, #[case] four: Vec<Pos>, #[case] eight: Vec<Pos>) {
+        let grid = grid();
+        assert_eq!(grid.neighbors4(pos).collect::<Vec<_>>(), four);
+        assert_eq!(grid.neighbors8(pos).collect::<Vec<_>>(), eight);
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = grid();
+        assert_eq!(grid.row(1), ['.', '@', 'c']);
+        assert_eq!(grid.column(2).collect::<String>(), ".c");
+        assert_eq!(grid.find_all(&'.').collect::<Vec<_>>(), [(2, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn test_transpose_and_rotate() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "a.\nb@\n.c");
+        assert_eq!(grid.rotate().to_string(), ".a\n@b\nc.");
+        let full_turn = grid.rotate().rotate().rotate().rotate();
+        assert_eq!(full_turn, grid);
+    }
+}
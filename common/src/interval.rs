@@ -0,0 +1,274 @@
+//! Sets of integers stored as sorted, disjoint ranges, for puzzles that deal
+//! in ranges far too large to enumerate.
+
+use std::{
+    fmt::Debug,
+    iter::Sum,
+    ops::{Add, RangeInclusive, Sub},
+};
+
+/// The integer types an [`IntervalSet`] can hold.
+pub trait Integer: Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> + Sum {
+    const ONE: Self;
+
+    fn checked_succ(self) -> Option<Self>;
+    fn checked_pred(self) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ONE: Self = 1;
+
+                fn checked_succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn checked_pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+            }
+        )*
+    };
+}
+
+integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+/// Ranges are kept sorted and merged on insert, so two ranges never
+/// overlap or touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every value of `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // the ranges from `first` to `last` overlap or touch the new one
+        let first = self
+            .ranges
+            .partition_point(|&(_, e)| e.checked_succ().is_some_and(|next| next < start));
+        let last = match end.checked_succ() {
+            Some(next) => self.ranges.partition_point(|&(s, _)| s <= next),
+            None => self.ranges.len(),
+        };
+        let merged = if first < last {
+            (
+                start.min(self.ranges[first].0),
+                end.max(self.ranges[last - 1].1),
+            )
+        } else {
+            (start, end)
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Takes every value of `range` out of the set.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self.ranges.partition_point(|&(_, e)| e < start);
+        let last = self.ranges.partition_point(|&(s, _)| s <= end);
+        // what is left of the first and last overlapping ranges
+        let mut kept = Vec::new();
+        if first < last {
+            let (s, _) = self.ranges[first];
+            if s < start {
+                kept.push((s, start.checked_pred().unwrap()));
+            }
+            let (_, e) = self.ranges[last - 1];
+            if e > end {
+                kept.push((end.checked_succ().unwrap(), e));
+            }
+        }
+        self.ranges.splice(first..last, kept);
+    }
+
+    /// A binary search over the ranges.
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.ranges.partition_point(|&(_, e)| e < value);
+        self.ranges.get(idx).is_some_and(|&(s, _)| s <= value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// How many values the set holds, which overflows `T` for a set of all of them.
+    pub fn len(&self) -> T {
+        self.ranges.iter().map(|&(s, e)| e - s + T::ONE).sum()
+    }
+
+    /// The ranges in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    /// The ranges missing between the first and the last value of the set.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.windows(2).map(|pair| {
+            let (_, end) = pair[0];
+            let (start, _) = pair[1];
+            end.checked_succ().unwrap()..=start.checked_pred().unwrap()
+        })
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut ours, mut theirs) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        let mut ranges = Vec::new();
+        while let (Some(&&(s1, e1)), Some(&&(s2, e2))) = (ours.peek(), theirs.peek()) {
+            let (start, end) = (s1.max(s2), e1.min(e2));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if e1 < e2 {
+                ours.next();
+            } else {
+                theirs.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// The values of `bounds` that are not in the set.
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> Self {
+        Self::from_iter([bounds]).difference(self)
+    }
+}
+
+impl<T: Integer> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, ranges: I) {
+        for range in ranges {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Integer> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = Self::new();
+        set.extend(ranges);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use rstest::rstest;
+
+    use super::*;
+
+    fn set(ranges: &[(u8, u8)]) -> IntervalSet<u8> {
+        ranges.iter().map(|&(s, e)| s..=e).collect()
+    }
+
+    #[rstest]
+    #[case(&[(3, 5), (10, 14), (16, 20), (12, 18)], &[(3, 5), (10, 20)])]
+    #[case(&[(1, 2), (5, 6), (9, 10), (0, 12)], &[(0, 12)])]
+    #[case(&[(1, 2), (3, 4)], &[(1, 4)])]
+    #[case(&[(250, 255), (0, 3), (4, 249)], &[(0, 255)])]
+    #[case(&[(5, 3)], &[])]
+    fn test_insert_normalizes(#[case] ranges: &[(u8, u8)], #[case] expected: &[(u8, u8)]) {
+        assert_eq!(set(ranges).ranges, expected);
+    }
+
+    #[test]
+    fn test_operations() {
+        let a = set(&[(1, 5), (10, 12)]);
+        let b = set(&[(4, 10), (20, 20)]);
+        assert_eq!(a.union(&b).ranges, [(1, 12), (20, 20)]);
+        assert_eq!(a.intersection(&b).ranges, [(4, 5), (10, 10)]);
+        assert_eq!(a.difference(&b).ranges, [(1, 3), (11, 12)]);
+        assert_eq!(a.complement(0..=255).ranges, [(0, 0), (6, 9), (13, 255)]);
+        assert_eq!(a.gaps().collect::<Vec<_>>(), [6..=9]);
+        assert_eq!(a.len(), 8);
+    }
+
+    /// A seeded random walk through inserts and removals on `u8`, checked
+    /// against the same operations on a `BTreeSet` after every step.
+    #[test]
+    fn test_against_btreeset() {
+        let mut rng = fastrand::Rng::with_seed(2025);
+        let mut random_range = || {
+            let start = rng.u8(..);
+            start..=start.saturating_add(rng.u8(..40))
+        };
+        let oracle = |set: &IntervalSet<u8>| set.iter().flatten().collect::<BTreeSet<_>>();
+        for _ in 0..50 {
+            let mut set = IntervalSet::new();
+            let mut values = BTreeSet::new();
+            let mut other = IntervalSet::new();
+            for step in 0..20 {
+                let range = random_range();
+                if step % 3 == 2 {
+                    set.remove(range.clone());
+                    values.retain(|value| !range.contains(value));
+                } else {
+                    set.insert(range.clone());
+                    values.extend(range);
+                }
+                other.insert(random_range());
+
+                assert_eq!(oracle(&set), values);
+                assert!(set.ranges.windows(2).all(|pair| pair[0].1 + 1 < pair[1].0));
+                assert!((0..=255).all(|value| set.contains(value) == values.contains(&value)));
+                if values.len() < 256 {
+                    assert_eq!(usize::from(set.len()), values.len());
+                }
+
+                let theirs = oracle(&other);
+                assert_eq!(oracle(&set.union(&other)), &values | &theirs);
+                assert_eq!(oracle(&set.intersection(&other)), &values & &theirs);
+                assert_eq!(oracle(&set.difference(&other)), &values - &theirs);
+                let everything = (0..=255).collect::<BTreeSet<_>>();
+                assert_eq!(oracle(&set.complement(0..=255)), &everything - &values);
+                let gaps = set.gaps().flatten().collect::<BTreeSet<_>>();
+                let (min, max) = (values.first(), values.last());
+                let expected = min.map_or_else(BTreeSet::new, |&min| {
+                    (min..=*max.unwrap())
+                        .filter(|v| !values.contains(v))
+                        .collect()
+                });
+                assert_eq!(gaps, expected);
+            }
+        }
+    }
+}
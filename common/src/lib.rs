@@ -0,0 +1,119 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use crate::{assume::Assumption, parse::ParseError};
+
+pub mod assume;
+pub mod build;
+pub mod cancel;
+mod day;
+pub mod disjoint_set;
+pub mod grid;
+pub mod interval;
+pub mod parse;
+
+pub use day::{AnyDay, Day, Prepared, Variant};
+
+/// One part of a day, or one of its named variants.
+#[derive(Clone, Copy)]
+pub struct Solver {
+    pub day: u8,
+    pub part: u8,
+    pub name: Option<&'static str>,
+    of: &'static dyn AnyDay,
+}
+
+impl Solver {
+    /// Every solver of `days`, in day order.
+    pub fn all(days: &'static [&'static dyn AnyDay]) -> Vec<Self> {
+        let mut solvers = days
+            .iter()
+            .flat_map(|&of| {
+                of.solvers().into_iter().map(move |(part, name)| Self {
+                    day: of.day(),
+                    part,
+                    name,
+                    of,
+                })
+            })
+            .collect::<Vec<_>>();
+        solvers.sort_by_key(|s| (s.day, s.part));
+        solvers
+    }
+
+    /// Parses `input` with the day's generator.
+    pub fn prepare(&self, input: &str) -> Result<Box<dyn Prepared>, ParseError> {
+        self.of.prepare(input, self.part, self.name)
+    }
+}
+
+impl fmt::Display for Solver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} - Part {}", self.day, self.part)?;
+        if let Some(name) = self.name {
+            write!(f, " - {name}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Every day registered in one year crate, generated by its build script.
+pub struct Year {
+    pub year: u32,
+    pub root: &'static str,
+    pub days: &'static [&'static dyn AnyDay],
+    pub solvers: LazyLock<Vec<Solver>>,
+    /// `(day, part)` pairs with an example answer in `examples/<year>/`.
+    pub tested: &'static [(u8, u8)],
+}
+
+impl Year {
+    /// Runs one solver on an example, like the real input, and panics
+    /// unless it gives `expected`.
+    pub fn check_example(
+        &self,
+        day: u8,
+        part: u8,
+        name: Option<&str>,
+        input: &str,
+        expected: &str,
+    ) {
+        let solver = self
+            .solvers
+            .iter()
+            .find(|s| (s.day, s.part, s.name) == (day, part, name))
+            .expect("solver is registered");
+        let prepared = solver
+            .prepare(input)
+            .unwrap_or_else(|e| panic!("{solver}: FAILED while generating: {e}"));
+        assert_eq!(prepared.run().to_string(), expected, "{solver}");
+    }
+
+    /// Checks `input` against the day's assumptions, `None` if it declares none.
+    pub fn assumptions(&self, day: u8, input: &str) -> Option<Vec<Assumption>> {
+        let of = self.days.iter().find(|of| of.day() == day)?;
+        Some(of.assumptions(input)).filter(|assumptions| !assumptions.is_empty())
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        Path::new(self.root)
+            .join("input")
+            .join(self.year.to_string())
+            .join(format!("day{day}.txt"))
+    }
+}
+
+/// Expands to one `#[test]` per example, part and variant, as listed in
+/// `examples/<year>/dayNN/answers.toml`.
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        #[cfg(test)]
+        mod examples {
+            include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+        }
+    };
+}
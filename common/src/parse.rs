@@ -0,0 +1,158 @@
+//! Errors for input generators, pointing at the spot where the input stopped
+//! making sense.
+
+use std::{error::Error, fmt, str::FromStr};
+
+use winnow::error::{ContextError, StrContext, StrContextValue};
+
+/// A generator's complaint about its input, with enough context to print a
+/// caret under the offending character.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based.
+    pub line: usize,
+    /// 1-based, in characters.
+    pub column: usize,
+    /// The whole offending line.
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Points at byte `offset` of `input`.
+    pub fn new(day: u8, input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |idx| offset + idx);
+        Self {
+            day,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Points at the start of `part`, which must be a slice of `input`.
+    pub fn at(day: u8, input: &str, part: &str, expected: impl Into<String>) -> Self {
+        Self::new(day, input, offset_in(input, part), expected)
+    }
+
+    /// Converts a winnow error from parsing `input` or one of its slices.
+    /// What was expected comes from the parsers' `StrContext::Expected`.
+    pub fn winnow(
+        day: u8,
+        input: &str,
+        error: &winnow::error::ParseError<&str, ContextError>,
+    ) -> Self {
+        let expected = error
+            .inner()
+            .context()
+            .filter_map(|context| match context {
+                StrContext::Expected(value) => Some(value.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let expected = if expected.is_empty() {
+            "end of input".to_owned()
+        } else {
+            expected.join(" or ")
+        };
+        let offset = offset_in(input, error.input()) + error.offset();
+        Self::new(day, input, offset, expected)
+    }
+}
+
+fn offset_in(input: &str, part: &str) -> usize {
+    (part.as_ptr() as usize).saturating_sub(input.as_ptr() as usize)
+}
+
+/// Shorthand for `StrContext::Expected` with a description, such as
+/// ``expected("`,`")`` or `expected("a number")`.
+pub fn expected(description: &'static str) -> StrContext {
+    StrContext::Expected(StrContextValue::Description(description))
+}
+
+/// Parses `field`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(day: u8, input: &str, field: &str) -> Result<T, ParseError> {
+    field
+        .parse()
+        .map_err(|_| ParseError::at(day, input, field, "a number"))
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "day {}: expected {} at line {}, column {}",
+            self.day, self.expected, self.line, self.column
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{gutter} | {}^", " ".repeat(self.column - 1))
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use winnow::{Parser, ascii::dec_uint, combinator::separated_pair};
+
+    use super::*;
+
+    #[rstest]
+    #[case("L68\nL30\nR48", 0, (1, 1, "L68"))]
+    #[case("L68\nL30\nR48", 5, (2, 2, "L30"))]
+    #[case("L68\nL30\nR48", 11, (3, 4, "R48"))]
+    #[case("L68\r\nX30", 5, (2, 1, "X30"))]
+    fn test_position(
+        #[case] input: &str,
+        #[case] offset: usize,
+        #[case] (line, column, snippet): (usize, usize, &str),
+    ) {
+        let error = ParseError::new(1, input, offset, "`L` or `R`");
+        assert_eq!((error.line, error.column), (line, column));
+        assert_eq!(error.snippet, snippet);
+    }
+
+    #[test]
+    fn test_display() {
+        let input = "7,1\n11,1\n11;7";
+        let error = ParseError::at(9, input, &input[11..], "`,`");
+        assert_eq!(
+            error.to_string(),
+            "day 9: expected `,` at line 3, column 3\n  |\n3 | 11;7\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn test_winnow() {
+        let input = "3-5\n10x14";
+        let line = &input[4..];
+        let error = separated_pair(
+            dec_uint::<_, u64, ContextError>.context(expected("a number")),
+            '-'.context(expected("`-`")),
+            dec_uint::<_, u64, ContextError>.context(expected("a number")),
+        )
+        .parse(line)
+        .unwrap_err();
+        let error = ParseError::winnow(5, input, &error);
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.expected, "`-`");
+    }
+
+    #[test]
+    fn test_number() {
+        let input = "1,x";
+        assert_eq!(number::<u32>(9, input, &input[..1]), Ok(1));
+        assert_eq!(number::<u32>(9, input, &input[2..]).unwrap_err().column, 3);
+    }
+}